@@ -1,41 +1,242 @@
 use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
 use std::str::FromStr;
 
+use itertools::{Either, Itertools};
+
 fn main() {
-    aoc_2025::aoc!(INPUT, part1, part2);
+    let rule = accessibility_rule();
+    #[cfg(feature = "part2")]
+    visualise(INPUT, rule).expect("Should visualise");
+    aoc_2025::aoc!(INPUT, part1(rule), part2(rule));
 }
 
 const INPUT: &str = include_str!("../input/input.day04");
 
 #[cfg(feature = "part1")]
-fn part1(input: &str) -> u64 {
+fn part1(input: &str, rule: AccessibilityRule) -> u64 {
     // Took 35 minutes 8,96 seconds
-    count_of_paper_rolls_accessible_by_a_forklift(input.parse().expect("Should parse department"))
+    count_of_paper_rolls_accessible_by_a_forklift(
+        input.parse().expect("Should parse department"),
+        rule,
+    )
 }
 
 #[cfg(feature = "part2")]
-fn part2(input: &str) -> u64 {
+fn part2(input: &str, rule: AccessibilityRule) -> u64 {
     // Took 20 minutes 23,20 seconds
-    count_of_paper_rolls_removable_repeatedly(input.parse().expect("Should parse department"))
+    count_of_paper_rolls_removable_repeatedly(input.parse().expect("Should parse department"), rule)
+}
+
+fn accessibility_rule() -> AccessibilityRule {
+    aoc_2025::option("rule")
+        .map(|rule| rule.parse().expect("Should parse accessibility rule"))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "part2")]
+fn visualise(input: &str, rule: AccessibilityRule) -> Result<(), visualisation::VisualiseError> {
+    let animate = aoc_2025::option("animate");
+    let ppm_frames = aoc_2025::option("ppm-frames");
+    let png_frames = aoc_2025::option("png-frames");
+    let gif = aoc_2025::option("gif");
+    if animate.is_none() && ppm_frames.is_none() && png_frames.is_none() && gif.is_none() {
+        return Ok(());
+    }
+    let department: PrintingDepartment = input.parse().expect("Should parse department");
+    let frames = department.removal_frames(rule);
+    if let Some(milliseconds) = animate {
+        let frame_duration = milliseconds
+            .parse()
+            .map(std::time::Duration::from_millis)
+            .expect("Should parse frame duration in milliseconds");
+        visualisation::animate_in_terminal(&frames, frame_duration)?;
+    }
+    if let Some(directory) = ppm_frames {
+        visualisation::write_ppm_frames(&frames, directory.as_ref())?;
+    }
+    if let Some(directory) = png_frames {
+        visualisation::write_png_frames(&frames, directory.as_ref())?;
+    }
+    if let Some(path) = gif {
+        visualisation::write_gif(&frames, path.as_ref())?;
+    }
+    Ok(())
 }
 
 #[cfg(feature = "part1")]
-fn count_of_paper_rolls_accessible_by_a_forklift(mut department: PrintingDepartment) -> u64 {
-    department.mark_removable()
+fn count_of_paper_rolls_accessible_by_a_forklift(
+    mut department: PrintingDepartment,
+    rule: AccessibilityRule,
+) -> u64 {
+    department.mark_removable(rule)
 }
 
 #[cfg(feature = "part2")]
-fn count_of_paper_rolls_removable_repeatedly(mut department: PrintingDepartment) -> u64 {
-    let mut count = 0;
-    loop {
-        department.mark_removable();
-        let new_count = department.remove_removable();
-        if new_count == 0 {
-            break;
+fn count_of_paper_rolls_removable_repeatedly(
+    mut department: PrintingDepartment,
+    rule: AccessibilityRule,
+) -> u64 {
+    department.remove_repeatedly(rule).len() as u64
+}
+
+#[cfg(feature = "part2")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct Removal {
+    row: usize,
+    column: usize,
+    round: usize,
+}
+
+/// A paper roll is accessible when fewer than `threshold` of its neighbours are paper rolls.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct AccessibilityRule {
+    threshold: u64,
+    neighbourhood: Neighbourhood,
+    edges: Edges,
+}
+
+impl Default for AccessibilityRule {
+    fn default() -> Self {
+        Self {
+            threshold: 4,
+            neighbourhood: Neighbourhood::Moore { radius: 1 },
+            edges: Edges::Bounded,
+        }
+    }
+}
+
+/// Parses comma separated `key=value` pairs like `threshold=3,shape=von-neumann,radius=2,edges=toroidal`,
+/// omitted keys keep their default.
+impl FromStr for AccessibilityRule {
+    type Err = ParseAccessibilityRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rule = Self::default();
+        let mut radius = None;
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (key, value) =
+                pair.split_once('=')
+                    .ok_or_else(|| ParseAccessibilityRuleError::MissingValue {
+                        pair: pair.to_string(),
+                    })?;
+            match key.trim() {
+                "threshold" => {
+                    rule.threshold = value.trim().parse().map_err(|error| {
+                        ParseAccessibilityRuleError::InvalidNumber {
+                            key: key.to_string(),
+                            source: error,
+                        }
+                    })?
+                }
+                "radius" => {
+                    radius = Some(value.trim().parse().map_err(|error| {
+                        ParseAccessibilityRuleError::InvalidNumber {
+                            key: key.to_string(),
+                            source: error,
+                        }
+                    })?)
+                }
+                "shape" => {
+                    rule.neighbourhood = match value.trim() {
+                        "von-neumann" => Neighbourhood::VonNeumann { radius: 1 },
+                        "moore" => Neighbourhood::Moore { radius: 1 },
+                        _ => {
+                            return Err(ParseAccessibilityRuleError::UnknownShape {
+                                value: value.to_string(),
+                            });
+                        }
+                    }
+                }
+                "edges" => {
+                    rule.edges = match value.trim() {
+                        "bounded" => Edges::Bounded,
+                        "toroidal" => Edges::Toroidal,
+                        _ => {
+                            return Err(ParseAccessibilityRuleError::UnknownEdges {
+                                value: value.to_string(),
+                            });
+                        }
+                    }
+                }
+                _ => {
+                    return Err(ParseAccessibilityRuleError::UnknownKey {
+                        key: key.to_string(),
+                    });
+                }
+            }
+        }
+        if let Some(new_radius) = radius {
+            match &mut rule.neighbourhood {
+                Neighbourhood::VonNeumann { radius } | Neighbourhood::Moore { radius } => {
+                    *radius = new_radius
+                }
+            }
+        }
+        Ok(rule)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum ParseAccessibilityRuleError {
+    #[error("Missing '=' in '{pair}'")]
+    MissingValue { pair: String },
+    #[error("Unknown key '{key}'")]
+    UnknownKey { key: String },
+    #[error("Failed to parse number of '{key}': {source}")]
+    InvalidNumber { key: String, source: ParseIntError },
+    #[error("Unknown shape '{value}'")]
+    UnknownShape { value: String },
+    #[error("Unknown edges '{value}'")]
+    UnknownEdges { value: String },
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Neighbourhood {
+    /// All cells within the given manhattan distance.
+    VonNeumann { radius: usize },
+    /// All cells within the given chebyshev distance.
+    Moore { radius: usize },
+}
+
+impl Neighbourhood {
+    fn radius(self) -> usize {
+        match self {
+            Self::VonNeumann { radius } | Self::Moore { radius } => radius,
+        }
+    }
+
+    fn offsets(self) -> impl Iterator<Item = (isize, isize)> {
+        let radius = self.radius() as isize;
+        (-radius..=radius)
+            .flat_map(move |row_offset| {
+                (-radius..=radius).map(move |column_offset| (row_offset, column_offset))
+            })
+            .filter(move |&offset| offset != (0, 0) && self.contains(offset))
+    }
+
+    fn contains(self, (row_offset, column_offset): (isize, isize)) -> bool {
+        match self {
+            Self::VonNeumann { radius } => {
+                row_offset.unsigned_abs() + column_offset.unsigned_abs() <= radius
+            }
+            Self::Moore { radius } => {
+                row_offset.unsigned_abs().max(column_offset.unsigned_abs()) <= radius
+            }
         }
-        count += new_count;
     }
-    count
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Edges {
+    /// Cells beyond the edges do not exist.
+    Bounded,
+    /// Cells beyond an edge wrap around to the opposite edge.
+    Toroidal,
+}
+
+fn wrap(index: usize, offset: isize, count: usize) -> usize {
+    (index as isize + offset).rem_euclid(count as isize) as usize
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -44,12 +245,13 @@ struct PrintingDepartment {
 }
 
 impl PrintingDepartment {
-    fn mark_removable(&mut self) -> u64 {
+    #[cfg(feature = "part1")]
+    fn mark_removable(&mut self, rule: AccessibilityRule) -> u64 {
         let mut count = 0;
         for y in 0..self.grid.len() {
             for x in 0..self.grid[y].len() {
                 if self.is_paper_roll(y, x) {
-                    if self.count_neighbours(y, x) < 4 {
+                    if self.count_neighbours(y, x, rule) < rule.threshold {
                         self.grid[y][x] = MaybePaperRoll::Removable;
                         count += 1;
                     } else {
@@ -61,56 +263,131 @@ impl PrintingDepartment {
         count
     }
 
+    /// Removes all accessible paper rolls round by round, until nothing is accessible anymore.
+    /// Only the neighbours of removed paper rolls are revisited, instead of rescanning the grid.
     #[cfg(feature = "part2")]
-    fn remove_removable(&mut self) -> u64 {
-        let mut count = 0;
+    fn remove_repeatedly(&mut self, rule: AccessibilityRule) -> Vec<Removal> {
+        let mut neighbour_counts = Vec::with_capacity(self.grid.len());
+        let mut queue = Vec::new();
         for y in 0..self.grid.len() {
+            let mut row_neighbour_counts = Vec::with_capacity(self.grid[y].len());
             for x in 0..self.grid[y].len() {
-                self.grid[y][x] = match self.grid[y][x] {
-                    MaybePaperRoll::MovementUnchecked | MaybePaperRoll::Irremovable => {
-                        MaybePaperRoll::MovementUnchecked
+                let count = self.count_neighbours(y, x, rule);
+                if self.is_paper_roll(y, x) {
+                    if count < rule.threshold {
+                        self.grid[y][x] = MaybePaperRoll::Removable;
+                        queue.push((y, x));
+                    } else {
+                        self.grid[y][x] = MaybePaperRoll::Irremovable;
                     }
-                    MaybePaperRoll::Removable => {
-                        count += 1;
-                        MaybePaperRoll::None
+                }
+                row_neighbour_counts.push(count);
+            }
+            neighbour_counts.push(row_neighbour_counts);
+        }
+        let mut removals = Vec::new();
+        let mut round = 0;
+        while !queue.is_empty() {
+            round += 1;
+            for &(row, column) in &queue {
+                self.grid[row][column] = MaybePaperRoll::None;
+                removals.push(Removal { row, column, round });
+            }
+            let mut next_queue = Vec::new();
+            for (y, x) in queue {
+                for (neighbour_y, neighbour_x) in self.neighbours(y, x, rule) {
+                    if self.grid[neighbour_y][neighbour_x] != MaybePaperRoll::Irremovable {
+                        continue;
                     }
-                    MaybePaperRoll::None => MaybePaperRoll::None,
-                };
+                    neighbour_counts[neighbour_y][neighbour_x] -= 1;
+                    if neighbour_counts[neighbour_y][neighbour_x] < rule.threshold {
+                        self.grid[neighbour_y][neighbour_x] = MaybePaperRoll::Removable;
+                        next_queue.push((neighbour_y, neighbour_x));
+                    }
+                }
             }
+            queue = next_queue;
         }
-        count
+        removals
     }
 
-    fn count_neighbours(&self, row_index: usize, column_index: usize) -> u64 {
-        let mut count = 0;
-        for check_row_relative_index in -1isize..=1 {
-            let Some(check_row_index) = row_index.checked_add_signed(check_row_relative_index)
-            else {
-                continue;
-            };
-            for check_column_relative_index in -1isize..=1 {
-                if check_row_relative_index == 0 && check_column_relative_index == 0 {
-                    continue;
-                }
-                let Some(check_column_index) =
-                    column_index.checked_add_signed(check_column_relative_index)
-                else {
-                    continue;
-                };
-                let is_neighbour = self
-                    .grid
-                    .get(check_row_index)
-                    .and_then(|row| {
-                        row.get(check_column_index)
-                            .map(MaybePaperRoll::is_paper_roll)
-                    })
-                    .unwrap_or(false);
-                if is_neighbour {
-                    count += 1;
-                }
+    /// Returns one snapshot before the first round, one per round with the paper rolls about to be
+    /// removed marked as removable and one after the last round with the remaining paper rolls
+    /// marked as irremovable.
+    #[cfg(feature = "part2")]
+    fn removal_frames(&self, rule: AccessibilityRule) -> Vec<PrintingDepartment> {
+        let removals = self.clone().remove_repeatedly(rule);
+        let mut current = self.clone();
+        for cell in current.grid.iter_mut().flatten() {
+            if cell.is_paper_roll() {
+                *cell = MaybePaperRoll::MovementUnchecked;
             }
         }
-        count
+        let mut frames = vec![current.clone()];
+        for round in removals.chunk_by(|left, right| left.round == right.round) {
+            for removal in round {
+                current.grid[removal.row][removal.column] = MaybePaperRoll::Removable;
+            }
+            frames.push(current.clone());
+            for removal in round {
+                current.grid[removal.row][removal.column] = MaybePaperRoll::None;
+            }
+        }
+        for cell in current.grid.iter_mut().flatten() {
+            if cell.is_paper_roll() {
+                *cell = MaybePaperRoll::Irremovable;
+            }
+        }
+        frames.push(current);
+        frames
+    }
+
+    fn count_neighbours(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        rule: AccessibilityRule,
+    ) -> u64 {
+        self.neighbours(row_index, column_index, rule)
+            .filter(|&(y, x)| self.grid[y][x].is_paper_roll())
+            .count() as u64
+    }
+
+    fn neighbours(
+        &self,
+        row_index: usize,
+        column_index: usize,
+        rule: AccessibilityRule,
+    ) -> impl Iterator<Item = (usize, usize)> + use<> {
+        let row_count = self.grid.len();
+        let column_count = self.grid.get(row_index).map_or(0, Vec::len);
+        let neighbours =
+            rule.neighbourhood
+                .offsets()
+                .filter_map(move |(row_offset, column_offset)| match rule.edges {
+                    Edges::Bounded => {
+                        let y = row_index.checked_add_signed(row_offset)?;
+                        let x = column_index.checked_add_signed(column_offset)?;
+                        (y < row_count && x < column_count).then_some((y, x))
+                    }
+                    Edges::Toroidal => Some((
+                        wrap(row_index, row_offset, row_count),
+                        wrap(column_index, column_offset, column_count),
+                    )),
+                });
+        let diameter = 2 * rule.neighbourhood.radius() + 1;
+        if rule.edges == Edges::Toroidal && (diameter > row_count || diameter > column_count) {
+            // Offsets wrapping around the whole grid reach some cells repeatedly, even the cell
+            // itself.
+            Either::Right(
+                neighbours
+                    .filter(move |&neighbour| neighbour != (row_index, column_index))
+                    .sorted_unstable()
+                    .dedup(),
+            )
+        } else {
+            Either::Left(neighbours)
+        }
     }
 
     fn is_paper_roll(&self, row_index: usize, column_index: usize) -> bool {
@@ -213,6 +490,171 @@ impl MaybePaperRoll {
     }
 }
 
+#[cfg(feature = "part2")]
+mod visualisation {
+    use std::fmt::{Display, Formatter};
+    use std::fs::File;
+    use std::io::{BufWriter, Write};
+    use std::path::Path;
+    use std::time::Duration;
+
+    use super::{MaybePaperRoll, PrintingDepartment};
+
+    const CELL_SIZE: usize = 4;
+    const FRAME_DELAY_IN_CENTISECONDS: u16 = 50;
+
+    pub fn animate_in_terminal(
+        frames: &[PrintingDepartment],
+        frame_duration: Duration,
+    ) -> Result<(), VisualiseError> {
+        let mut stdout = std::io::stdout().lock();
+        for (index, frame) in frames.iter().enumerate() {
+            // Clear the screen and move the cursor to the top left
+            write!(stdout, "\x1b[2J\x1b[H")?;
+            writeln!(stdout, "Frame {}/{}", index + 1, frames.len())?;
+            write!(stdout, "{}", Ansi(frame))?;
+            stdout.flush()?;
+            std::thread::sleep(frame_duration);
+        }
+        Ok(())
+    }
+
+    pub fn write_ppm_frames(
+        frames: &[PrintingDepartment],
+        directory: &Path,
+    ) -> Result<(), VisualiseError> {
+        std::fs::create_dir_all(directory)?;
+        for (index, frame) in frames.iter().enumerate() {
+            let (width, height) = dimensions(frame);
+            let mut writer = BufWriter::new(File::create(
+                directory.join(format!("frame{index:04}.ppm")),
+            )?);
+            write!(writer, "P6\n{width} {height}\n255\n")?;
+            writer.write_all(&rgb_pixels(frame))?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn write_png_frames(
+        frames: &[PrintingDepartment],
+        directory: &Path,
+    ) -> Result<(), VisualiseError> {
+        std::fs::create_dir_all(directory)?;
+        for (index, frame) in frames.iter().enumerate() {
+            let (width, height) = dimensions(frame);
+            let writer = BufWriter::new(File::create(
+                directory.join(format!("frame{index:04}.png")),
+            )?);
+            let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .write_header()?
+                .write_image_data(&rgb_pixels(frame))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_gif(frames: &[PrintingDepartment], path: &Path) -> Result<(), VisualiseError> {
+        let Some(first_frame) = frames.first() else {
+            return Ok(());
+        };
+        let (width, height) = dimensions(first_frame);
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(VisualiseError::TooLargeForGif { width, height });
+        };
+        let palette = PALETTE.iter().flat_map(|(_, rgb)| *rgb).collect::<Vec<_>>();
+        let mut encoder =
+            gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for frame in frames {
+            encoder.write_frame(&gif::Frame {
+                width,
+                height,
+                delay: FRAME_DELAY_IN_CENTISECONDS,
+                buffer: pixels(frame, |cell| [palette_index(cell)]).into(),
+                ..gif::Frame::default()
+            })?;
+        }
+        Ok(())
+    }
+
+    const PALETTE: [(MaybePaperRoll, [u8; 3]); 4] = [
+        (MaybePaperRoll::None, [0x20, 0x20, 0x20]),
+        (MaybePaperRoll::MovementUnchecked, [0xe8, 0xe4, 0xd8]),
+        (MaybePaperRoll::Removable, [0xd0, 0x30, 0x30]),
+        (MaybePaperRoll::Irremovable, [0x30, 0xb0, 0x50]),
+    ];
+
+    fn palette_index(cell: MaybePaperRoll) -> u8 {
+        PALETTE
+            .iter()
+            .position(|(palette_cell, _)| *palette_cell == cell)
+            .expect("Should have every cell in palette") as u8
+    }
+
+    fn rgb(cell: MaybePaperRoll) -> [u8; 3] {
+        PALETTE[palette_index(cell) as usize].1
+    }
+
+    fn dimensions(frame: &PrintingDepartment) -> (usize, usize) {
+        let columns = frame.grid.first().map_or(0, Vec::len);
+        (columns * CELL_SIZE, frame.grid.len() * CELL_SIZE)
+    }
+
+    fn rgb_pixels(frame: &PrintingDepartment) -> Vec<u8> {
+        pixels(frame, rgb)
+    }
+
+    fn pixels<const N: usize>(
+        frame: &PrintingDepartment,
+        channels: impl Fn(MaybePaperRoll) -> [u8; N],
+    ) -> Vec<u8> {
+        frame
+            .grid
+            .iter()
+            .flat_map(|row| std::iter::repeat_n(row, CELL_SIZE))
+            .flat_map(|row| {
+                row.iter()
+                    .flat_map(|cell| std::iter::repeat_n(channels(*cell), CELL_SIZE))
+            })
+            .flatten()
+            .collect()
+    }
+
+    struct Ansi<'a>(&'a PrintingDepartment);
+
+    impl Display for Ansi<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            for row in &self.0.grid {
+                for cell in row {
+                    match cell {
+                        MaybePaperRoll::MovementUnchecked => write!(f, "@")?,
+                        MaybePaperRoll::Removable => write!(f, "\x1b[1;31mX\x1b[0m")?,
+                        MaybePaperRoll::Irremovable => write!(f, "\x1b[32mO\x1b[0m")?,
+                        MaybePaperRoll::None => write!(f, "\x1b[90m.\x1b[0m")?,
+                    }
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum VisualiseError {
+        #[error("Failed to write: {0}")]
+        Io(#[from] std::io::Error),
+        #[error("Failed to encode png: {0}")]
+        Png(#[from] png::EncodingError),
+        #[error("Failed to encode gif: {0}")]
+        Gif(#[from] gif::EncodingError),
+        #[error("Frames of {width}x{height} pixels are too large for a gif")]
+        TooLargeForGif { width: usize, height: usize },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,7 +674,7 @@ mod tests {
 @.@.@@@.@.";
 
         // Act
-        let part1 = part1(input);
+        let part1 = part1(input, AccessibilityRule::default());
 
         // Assert
         assert_eq!(part1, 13);
@@ -253,9 +695,117 @@ mod tests {
 @.@.@@@.@.";
 
         // Act
-        let part2 = part2(input);
+        let part2 = part2(input, AccessibilityRule::default());
 
         // Assert
         assert_eq!(part2, 43);
     }
+
+    #[test]
+    fn test_remove_repeatedly_rounds() {
+        // Arrange
+        let mut department: PrintingDepartment = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@."
+            .parse()
+            .expect("Should parse department");
+
+        // Act
+        let removals = department.remove_repeatedly(AccessibilityRule::default());
+
+        // Assert
+        let removals_per_round = removals.iter().fold(Vec::new(), |mut acc, removal| {
+            if acc.len() < removal.round {
+                acc.resize(removal.round, 0);
+            }
+            acc[removal.round - 1] += 1;
+            acc
+        });
+        assert_eq!(removals_per_round, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+        assert!(removals.is_sorted_by_key(|removal| removal.round));
+    }
+
+    #[test]
+    fn test_mark_removable_with_rules() {
+        // Arrange
+        let department: PrintingDepartment = "@@@
+@.@
+@@@"
+        .parse()
+        .expect("Should parse department");
+        let rules = [
+            AccessibilityRule::default(),
+            "edges=toroidal".parse().expect("Should parse rule"),
+            "threshold=3,shape=von-neumann"
+                .parse()
+                .expect("Should parse rule"),
+            "threshold=3,shape=von-neumann,radius=2"
+                .parse()
+                .expect("Should parse rule"),
+        ];
+
+        // Act
+        let counts = rules.map(|rule| department.clone().mark_removable(rule));
+
+        // Assert
+        assert_eq!(counts, [4, 0, 8, 0]);
+    }
+
+    #[test]
+    fn test_toroidal_radius_beyond_grid() {
+        // Arrange
+        let department: PrintingDepartment = "@@@
+@.@
+@@@"
+        .parse()
+        .expect("Should parse department");
+        let rule = |threshold| AccessibilityRule {
+            threshold,
+            neighbourhood: Neighbourhood::Moore { radius: 2 },
+            edges: Edges::Toroidal,
+        };
+
+        // Act
+        let neighbour_count = department.count_neighbours(0, 0, rule(8));
+        let counts = [7, 8].map(|threshold| department.clone().mark_removable(rule(threshold)));
+
+        // Assert
+        assert_eq!(neighbour_count, 7);
+        assert_eq!(counts, [0, 8]);
+    }
+
+    #[test]
+    fn test_removal_frames() {
+        // Arrange
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        let department: PrintingDepartment = input.parse().expect("Should parse department");
+
+        // Act
+        let frames = department.removal_frames(AccessibilityRule::default());
+
+        // Assert
+        assert_eq!(frames.len(), 11);
+        assert_eq!(frames[0].to_string().trim_end(), input);
+        assert_eq!(frames[1].to_string().matches('X').count(), 13);
+        assert_eq!(
+            frames[10].to_string().matches('O').count(),
+            input.matches('@').count() - 43
+        );
+    }
 }
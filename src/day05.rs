@@ -1,111 +1,229 @@
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "part1")]
+use std::fs::File;
+use std::io::BufRead;
+#[cfg(feature = "part1")]
+use std::io::{BufReader, BufWriter};
 use std::num::ParseIntError;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
+use aoc_2025::interval_set::IntervalSet;
+
 fn main() {
-    aoc_2025::aoc!(INPUT, part1, part2);
+    let reversed_range_policy = reversed_range_policy();
+    #[cfg(feature = "part1")]
+    {
+        for (option, format) in [
+            ("freshness-csv", freshness_report::ReportFormat::Csv),
+            ("freshness-json", freshness_report::ReportFormat::Json),
+        ] {
+            if let Some(path) = aoc_2025::option(option) {
+                let file = File::create(path).expect("Should create freshness report");
+                freshness_report::export(
+                    database(reversed_range_policy),
+                    format,
+                    BufWriter::new(file),
+                )
+                .expect("Should export freshness report");
+            }
+        }
+        if aoc_2025::option("inventory").is_some() {
+            let answer = count_fresh_available_ingredients(database(reversed_range_policy))
+                .expect("Should parse fine");
+            println!("The answer to part 1 for the inventory is: {answer}");
+            return;
+        }
+    }
+    aoc_2025::aoc!(
+        INPUT,
+        part1(reversed_range_policy),
+        part2(reversed_range_policy)
+    );
 }
 
 const INPUT: &str = include_str!("../input/input.day05");
 
+/// Returns the inventory given via command line or else the puzzle input.
 #[cfg(feature = "part1")]
-fn part1(input: &str) -> u64 {
+fn database(reversed_range_policy: ReversedRangePolicy) -> DatabaseReader<Box<dyn BufRead>> {
+    DatabaseReader::new(match aoc_2025::option("inventory") {
+        Some(path) => Box::new(BufReader::new(
+            File::open(path).expect("Should open inventory"),
+        )),
+        None => Box::new(INPUT.as_bytes()) as Box<dyn BufRead>,
+    })
+    .with_reversed_range_policy(reversed_range_policy)
+}
+
+fn reversed_range_policy() -> ReversedRangePolicy {
+    aoc_2025::option("reversed-ranges")
+        .map(|policy| policy.parse().expect("Should parse reversed range policy"))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "part1")]
+fn part1(input: &str, reversed_range_policy: ReversedRangePolicy) -> u64 {
     // Took 16 minutes 30,47 seconds
-    Database::from_str(input)
-        .expect("Should parse fine")
-        .count_fresh_available_ingredients()
+    count_fresh_available_ingredients(
+        DatabaseReader::new(input.as_bytes()).with_reversed_range_policy(reversed_range_policy),
+    )
+    .expect("Should parse fine")
 }
 
 #[cfg(feature = "part2")]
-fn part2(input: &str) -> u64 {
+fn part2(input: &str, reversed_range_policy: ReversedRangePolicy) -> u128 {
     // Took 15 minutes 51 seconds
-    Database::from_str(input)
-        .expect("Should parse fine")
-        .count_unique_fresh_ingredient_ids()
+    count_unique_fresh_ingredient_ids(
+        DatabaseReader::new(input.as_bytes()).with_reversed_range_policy(reversed_range_policy),
+    )
+    .expect("Should parse fine")
 }
 
-#[derive(Clone, Eq, PartialEq, Hash, Debug)]
-struct Database {
-    fresh_ingredient_ranges: Box<[IngredientIdRange]>,
-    available_ingredients: Box<[IngredientId]>,
+#[cfg(feature = "part1")]
+fn count_fresh_available_ingredients(
+    database: DatabaseReader<impl BufRead>,
+) -> Result<u64, ParseDatabaseError> {
+    let (fresh_ingredient_ranges, mut available_ingredients) =
+        database.read_fresh_ingredient_ranges()?;
+    let fresh_ingredient_ids = fresh_ingredient_ids(&fresh_ingredient_ranges);
+    available_ingredients.try_fold(0, |count, available_ingredient| {
+        Ok(count + u64::from(fresh_ingredient_ids.contains(available_ingredient?.0)))
+    })
 }
 
-impl Database {
-    #[cfg(feature = "part1")]
-    fn count_fresh_available_ingredients(&self) -> u64 {
-        self.available_ingredients
-            .iter()
-            .filter(|available_ingredient| {
-                self.fresh_ingredient_ranges
-                    .iter()
-                    .any(|fresh_ingredient_range| {
-                        fresh_ingredient_range.contains(available_ingredient)
-                    })
-            })
-            .count() as u64
-    }
+#[cfg(feature = "part2")]
+fn count_unique_fresh_ingredient_ids(
+    database: DatabaseReader<impl BufRead>,
+) -> Result<u128, ParseDatabaseError> {
+    let (fresh_ingredient_ranges, _) = database.read_fresh_ingredient_ranges()?;
+    Ok(fresh_ingredient_ids(&fresh_ingredient_ranges).count())
+}
 
-    #[cfg(feature = "part2")]
-    fn count_unique_fresh_ingredient_ids(&self) -> u64 {
-        let mut output = self.fresh_ingredient_ranges.to_vec();
-        loop {
-            let previous = output.len();
-            output = output.into_iter().fold(Vec::new(), |mut acc, value| {
-                let mut found = false;
-                for range in &mut acc {
-                    if let Some(merged) = range.merge(&value) {
-                        *range = merged;
-                        found = true;
-                        break;
-                    }
-                }
-                if !found {
-                    acc.push(value);
-                }
-                acc
-            });
-            if previous == output.len() {
-                break;
-            }
+fn fresh_ingredient_ids(fresh_ingredient_ranges: &[IngredientIdRange]) -> IntervalSet<u64> {
+    fresh_ingredient_ranges
+        .iter()
+        .map(|range| range.to_range())
+        .collect()
+}
+
+/// Reads a database line by line, first the fresh ingredient ranges and then the available
+/// ingredients, without holding more than the current line in memory.
+struct DatabaseReader<R> {
+    reader: R,
+    line: String,
+    lines_read: usize,
+    reversed_range_policy: ReversedRangePolicy,
+}
+
+impl<R: BufRead> DatabaseReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            lines_read: 0,
+            reversed_range_policy: ReversedRangePolicy::default(),
         }
-        output.iter().map(|range| range.len()).sum::<u64>()
     }
-}
 
-impl FromStr for Database {
-    type Err = ParseDatabaseError;
+    fn with_reversed_range_policy(mut self, reversed_range_policy: ReversedRangePolicy) -> Self {
+        self.reversed_range_policy = reversed_range_policy;
+        self
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Reads the fresh ingredient ranges until the blank separator line or until the first line
+    /// without a range delimiter, which is then treated as first available ingredient.
+    fn read_fresh_ingredient_ranges(
+        mut self,
+    ) -> Result<(Box<[IngredientIdRange]>, AvailableIngredients<R>), ParseDatabaseError> {
         let mut fresh_ingredient_ranges = Vec::new();
-        let mut available_ingredients = Vec::new();
-        let mut separator_line_found = false;
-        for (index, line) in s.lines().enumerate() {
+        let mut first_available_ingredient = None;
+        let reversed_range_policy = self.reversed_range_policy;
+        while let Some((index, line)) = self.next_line()? {
             if line.is_empty() {
-                separator_line_found = true;
-            } else if separator_line_found {
-                available_ingredients.push(line.parse().map_err(|error| {
-                    ParseDatabaseError::ParseAvailableIngredientId {
-                        index,
-                        source: error,
-                    }
-                })?);
-            } else {
-                fresh_ingredient_ranges.push(line.parse().map_err(|error| {
+                break;
+            }
+            if !line.contains('-') {
+                first_available_ingredient = Some(parse_available_ingredient(index, line));
+                break;
+            }
+            fresh_ingredient_ranges.push(
+                IngredientIdRange::parse(line, reversed_range_policy).map_err(|error| {
                     ParseDatabaseError::ParseFreshIngredientRange {
                         index,
                         source: error,
                     }
-                })?);
+                })?,
+            );
+        }
+        Ok((
+            fresh_ingredient_ranges.into_boxed_slice(),
+            AvailableIngredients {
+                database: self,
+                first_available_ingredient,
+            },
+        ))
+    }
+
+    fn next_line(&mut self) -> Result<Option<(usize, &str)>, ParseDatabaseError> {
+        let index = self.lines_read;
+        self.line.clear();
+        let read =
+            self.reader
+                .read_line(&mut self.line)
+                .map_err(|error| ParseDatabaseError::Read {
+                    index,
+                    source: error,
+                })?;
+        if read == 0 {
+            return Ok(None);
+        }
+        self.lines_read += 1;
+        Ok(Some((index, self.line.trim_end_matches(['\n', '\r']))))
+    }
+}
+
+struct AvailableIngredients<R> {
+    database: DatabaseReader<R>,
+    first_available_ingredient: Option<Result<IngredientId, ParseDatabaseError>>,
+}
+
+impl<R: BufRead> Iterator for AvailableIngredients<R> {
+    type Item = Result<IngredientId, ParseDatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first_available_ingredient) = self.first_available_ingredient.take() {
+            return Some(first_available_ingredient);
+        }
+        loop {
+            match self.database.next_line() {
+                Err(error) => return Some(Err(error)),
+                Ok(None) => return None,
+                Ok(Some((_, ""))) => continue,
+                Ok(Some((index, line))) => return Some(parse_available_ingredient(index, line)),
             }
         }
-        Ok(Self {
-            fresh_ingredient_ranges: fresh_ingredient_ranges.into_boxed_slice(),
-            available_ingredients: available_ingredients.into_boxed_slice(),
-        })
     }
 }
 
+fn parse_available_ingredient(
+    index: usize,
+    line: &str,
+) -> Result<IngredientId, ParseDatabaseError> {
+    line.parse()
+        .map_err(|error| ParseDatabaseError::ParseAvailableIngredientId {
+            index,
+            source: error,
+        })
+}
+
 #[derive(thiserror::Error, Debug)]
 enum ParseDatabaseError {
+    #[error("Failed reading line {index}: {source}")]
+    Read {
+        index: usize,
+        source: std::io::Error,
+    },
     #[error("Failed parsing line {index} as fresh ingredient range: {source}")]
     ParseFreshIngredientRange {
         index: usize,
@@ -125,26 +243,71 @@ struct IngredientIdRange {
 }
 
 impl IngredientIdRange {
-    #[cfg(feature = "part1")]
-    fn contains(&self, id: &IngredientId) -> bool {
-        self.from <= *id && *id <= self.inclusive_to
+    fn parse(
+        s: &str,
+        reversed_range_policy: ReversedRangePolicy,
+    ) -> Result<Self, ParseIngredientRangeError> {
+        let (from, to) = s
+            .split_once('-')
+            .ok_or(ParseIngredientRangeError::MissingDelimiter)?;
+        let from = from.parse().map_err(ParseIngredientRangeError::ParseFrom)?;
+        let inclusive_to = to
+            .parse()
+            .map_err(ParseIngredientRangeError::ParseInclusiveTo)?;
+        match (from <= inclusive_to, reversed_range_policy) {
+            (true, _) => Ok(Self { from, inclusive_to }),
+            (false, ReversedRangePolicy::Swap) => Ok(Self {
+                from: inclusive_to,
+                inclusive_to: from,
+            }),
+            (false, ReversedRangePolicy::Reject) => {
+                Err(ParseIngredientRangeError::Reversed { from, inclusive_to })
+            }
+        }
     }
 
-    #[cfg(feature = "part2")]
-    fn merge(&self, other: &Self) -> Option<Self> {
-        if self.from <= other.inclusive_to && self.inclusive_to >= other.from {
-            Some(Self {
-                from: self.from.min(other.from),
-                inclusive_to: self.inclusive_to.max(other.inclusive_to),
-            })
-        } else {
-            None
+    fn to_range(self) -> RangeInclusive<u64> {
+        self.from.0..=self.inclusive_to.0
+    }
+
+    /// Returns the count of ids inside the range, which is `2^64` for the full span.
+    #[allow(dead_code)]
+    fn len(self) -> u128 {
+        u128::from(self.inclusive_to.0) - u128::from(self.from.0) + 1
+    }
+}
+
+/// What to do with ranges whose `from` is greater than their `inclusive_to`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+enum ReversedRangePolicy {
+    #[default]
+    Reject,
+    Swap,
+}
+
+impl FromStr for ReversedRangePolicy {
+    type Err = ParseReversedRangePolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(Self::Reject),
+            "swap" => Ok(Self::Swap),
+            _ => Err(ParseReversedRangePolicyError::Unknown {
+                value: s.to_string(),
+            }),
         }
     }
+}
 
-    #[cfg(feature = "part2")]
-    fn len(&self) -> u64 {
-        self.inclusive_to.0 - self.from.0 + 1
+#[derive(thiserror::Error, Debug)]
+enum ParseReversedRangePolicyError {
+    #[error("Unknown reversed range policy '{value}'")]
+    Unknown { value: String },
+}
+
+impl Display for IngredientIdRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.from, self.inclusive_to)
     }
 }
 
@@ -152,15 +315,7 @@ impl FromStr for IngredientIdRange {
     type Err = ParseIngredientRangeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (from, to) = s
-            .split_once('-')
-            .ok_or(ParseIngredientRangeError::MissingDelimiter)?;
-        Ok(Self {
-            from: from.parse().map_err(ParseIngredientRangeError::ParseFrom)?,
-            inclusive_to: to
-                .parse()
-                .map_err(ParseIngredientRangeError::ParseInclusiveTo)?,
-        })
+        Self::parse(s, ReversedRangePolicy::Reject)
     }
 }
 
@@ -172,11 +327,22 @@ enum ParseIngredientRangeError {
     ParseFrom(#[source] ParseIngredientIdError),
     #[error("Failed to parse inclusive to: {0}")]
     ParseInclusiveTo(#[source] ParseIngredientIdError),
+    #[error("From {from} is greater than inclusive to {inclusive_to}")]
+    Reversed {
+        from: IngredientId,
+        inclusive_to: IngredientId,
+    },
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 struct IngredientId(u64);
 
+impl Display for IngredientId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl FromStr for IngredientId {
     type Err = ParseIngredientIdError;
 
@@ -191,6 +357,129 @@ enum ParseIngredientIdError {
     Parse(#[from] ParseIntError),
 }
 
+#[cfg(feature = "part1")]
+mod freshness_report {
+    use std::io::{BufRead, Write};
+
+    use aoc_2025::interval_set::IntervalSet;
+    use itertools::Itertools;
+
+    use super::{
+        DatabaseReader, IngredientId, IngredientIdRange, ParseDatabaseError, fresh_ingredient_ids,
+    };
+
+    #[derive(Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct Freshness {
+        pub ingredient: IngredientId,
+        pub covering_ranges: Vec<IngredientIdRange>,
+    }
+
+    impl Freshness {
+        pub fn is_fresh(&self) -> bool {
+            !self.covering_ranges.is_empty()
+        }
+    }
+
+    pub struct FreshnessIndex {
+        ranges_by_from: Box<[IngredientIdRange]>,
+        fresh_ingredient_ids: IntervalSet<u64>,
+    }
+
+    impl FreshnessIndex {
+        pub fn new(fresh_ingredient_ranges: &[IngredientIdRange]) -> Self {
+            let mut ranges_by_from = Box::<[IngredientIdRange]>::from(fresh_ingredient_ranges);
+            ranges_by_from.sort_by_key(|range| range.from);
+            Self {
+                fresh_ingredient_ids: fresh_ingredient_ids(&ranges_by_from),
+                ranges_by_from,
+            }
+        }
+
+        /// Spoiled ingredients are answered in `O(log n)`, only fresh ones scan the ranges
+        /// starting at or before the ingredient for the covering ones.
+        pub fn query(&self, ingredient: IngredientId) -> Freshness {
+            let covering_ranges = if self.fresh_ingredient_ids.contains(ingredient.0) {
+                let candidates_end = self
+                    .ranges_by_from
+                    .partition_point(|range| range.from <= ingredient);
+                self.ranges_by_from[..candidates_end]
+                    .iter()
+                    .filter(|range| ingredient <= range.inclusive_to)
+                    .copied()
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Freshness {
+                ingredient,
+                covering_ranges,
+            }
+        }
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub enum ReportFormat {
+        /// One line per available ingredient with its covering ranges separated by `;`.
+        Csv,
+        /// An array with one object per available ingredient.
+        Json,
+    }
+
+    pub fn export(
+        database: DatabaseReader<impl BufRead>,
+        format: ReportFormat,
+        mut writer: impl Write,
+    ) -> Result<(), ExportFreshnessReportError> {
+        let (fresh_ingredient_ranges, available_ingredients) =
+            database.read_fresh_ingredient_ranges()?;
+        let index = FreshnessIndex::new(&fresh_ingredient_ranges);
+        match format {
+            ReportFormat::Csv => writeln!(writer, "ingredient_id,fresh,covering_ranges")?,
+            ReportFormat::Json => write!(writer, "[")?,
+        }
+        for (position, available_ingredient) in available_ingredients.enumerate() {
+            let freshness = index.query(available_ingredient?);
+            match format {
+                ReportFormat::Csv => writeln!(
+                    writer,
+                    "{},{},{}",
+                    freshness.ingredient,
+                    freshness.is_fresh(),
+                    freshness.covering_ranges.iter().join(";")
+                )?,
+                ReportFormat::Json => write!(
+                    writer,
+                    "{}\n  {{\"ingredient_id\":{},\"fresh\":{},\"covering_ranges\":[{}]}}",
+                    if position == 0 { "" } else { "," },
+                    freshness.ingredient,
+                    freshness.is_fresh(),
+                    freshness
+                        .covering_ranges
+                        .iter()
+                        .map(|range| format!(
+                            "{{\"from\":{},\"inclusive_to\":{}}}",
+                            range.from, range.inclusive_to
+                        ))
+                        .join(",")
+                )?,
+            }
+        }
+        if format == ReportFormat::Json {
+            writeln!(writer, "\n]")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum ExportFreshnessReportError {
+        #[error("Failed to read database: {0}")]
+        Parse(#[from] ParseDatabaseError),
+        #[error("Failed to write report: {0}")]
+        Write(#[from] std::io::Error),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,7 +500,7 @@ mod tests {
 32";
 
         // Act
-        let part1 = part1(input);
+        let part1 = part1(input, ReversedRangePolicy::default());
 
         // Assert
         assert_eq!(part1, 3);
@@ -226,9 +515,99 @@ mod tests {
 12-18";
 
         // Act
-        let part2 = part2(input);
+        let part2 = part2(input, ReversedRangePolicy::default());
 
         // Assert
         assert_eq!(part2, 14);
     }
+
+    #[test]
+    fn test_stream_without_separator_line() {
+        // Arrange
+        let input = "3-5\r\n10-14\r\n16-20\r\n12-18\r\n1\r\n5\r\n8\r\n11\r\n17\r\n32\r\n";
+
+        // Act
+        let count = count_fresh_available_ingredients(DatabaseReader::new(input.as_bytes()));
+
+        // Assert
+        assert_eq!(count.expect("Should parse fine"), 3);
+    }
+
+    #[test]
+    fn test_freshness_report() {
+        // Arrange
+        let input = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+17";
+        let mut csv = Vec::new();
+        let mut json = Vec::new();
+
+        // Act
+        freshness_report::export(
+            DatabaseReader::new(input.as_bytes()),
+            freshness_report::ReportFormat::Csv,
+            &mut csv,
+        )
+        .expect("Should export csv");
+        freshness_report::export(
+            DatabaseReader::new(input.as_bytes()),
+            freshness_report::ReportFormat::Json,
+            &mut json,
+        )
+        .expect("Should export json");
+
+        // Assert
+        assert_eq!(
+            String::from_utf8(csv).expect("Should be utf-8"),
+            "ingredient_id,fresh,covering_ranges
+1,false,
+5,true,3-5
+17,true,12-18;16-20
+"
+        );
+        assert_eq!(
+            String::from_utf8(json).expect("Should be utf-8"),
+            r#"[
+  {"ingredient_id":1,"fresh":false,"covering_ranges":[]},
+  {"ingredient_id":5,"fresh":true,"covering_ranges":[{"from":3,"inclusive_to":5}]},
+  {"ingredient_id":17,"fresh":true,"covering_ranges":[{"from":12,"inclusive_to":18},{"from":16,"inclusive_to":20}]}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn test_reversed_and_full_span_ranges() {
+        // Arrange
+        let input = "5-3
+0-18446744073709551615";
+
+        // Act
+        let rejected = count_unique_fresh_ingredient_ids(DatabaseReader::new(input.as_bytes()));
+        let swapped = count_unique_fresh_ingredient_ids(
+            DatabaseReader::new(input.as_bytes())
+                .with_reversed_range_policy(ReversedRangePolicy::Swap),
+        );
+
+        // Assert
+        assert!(matches!(
+            rejected,
+            Err(ParseDatabaseError::ParseFreshIngredientRange {
+                index: 0,
+                source: ParseIngredientRangeError::Reversed { .. },
+            })
+        ));
+        assert_eq!(swapped.expect("Should parse fine"), 1 << 64);
+        assert_eq!(
+            IngredientIdRange::from_str("0-18446744073709551615")
+                .expect("Should parse")
+                .len(),
+            1 << 64
+        );
+    }
 }
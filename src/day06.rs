@@ -1,120 +1,340 @@
+use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
+use std::ops::Range;
 use std::str::FromStr;
 
+use aoc_2025::number_type::{AnyNumber, NumberType};
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, ToPrimitive, Zero};
+
 fn main() {
-    aoc_2025::aoc!(INPUT, part1, part2);
+    let number_type = number_type();
+    aoc_2025::aoc!(INPUT, part1(number_type), part2(number_type));
 }
 
 const INPUT: &str = include_str!("../input/input.day06");
 
 #[cfg(feature = "part1")]
-fn part1(input: &str) -> u64 {
+fn part1(input: &str, number_type: NumberType) -> AnyNumber {
     // Took 24 minutes 46,45 seconds
     Worksheet::parse(input, ParseKind::TopToBottom)
         .expect("Should parse")
-        .grand_total()
+        .grand_total_as(number_type)
+        .expect("Should solve")
 }
 
 #[cfg(feature = "part2")]
-fn part2(input: &str) -> u64 {
+fn part2(input: &str, number_type: NumberType) -> AnyNumber {
     // Took 54 minutes 40,35 seconds
     Worksheet::parse(input, ParseKind::RightToLeft)
         .expect("Should parse")
-        .grand_total()
+        .grand_total_as(number_type)
+        .expect("Should solve")
+}
+
+fn number_type() -> NumberType {
+    aoc_2025::option("numbers")
+        .map(|number_type| number_type.parse().expect("Should parse number type"))
+        .unwrap_or_default()
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 struct Worksheet(Box<[Problem]>);
 
 impl Worksheet {
-    fn grand_total(&self) -> u64 {
+    fn grand_total_as(&self, number_type: NumberType) -> Result<AnyNumber, SolveWorksheetError> {
+        Ok(match number_type {
+            NumberType::U64 => AnyNumber::U64(self.grand_total()?),
+            NumberType::U128 => AnyNumber::U128(self.grand_total()?),
+            NumberType::Big => AnyNumber::Big(self.grand_total()?),
+        })
+    }
+
+    fn grand_total<N: Number>(&self) -> Result<N, SolveWorksheetError> {
         self.0
             .iter()
-            .map(Problem::solve)
-            .reduce(|left, right| left.checked_add(right).expect("Should not overflow"))
-            .expect("Should not be empty")
+            .enumerate()
+            .try_fold(N::zero(), |total, (index, problem)| {
+                let solution =
+                    problem
+                        .solve::<N>()
+                        .map_err(|error| SolveWorksheetError::Problem {
+                            index,
+                            source: error,
+                        })?;
+                total
+                    .checked_add(&solution)
+                    .ok_or(SolveWorksheetError::GrandTotalOverflow { index })
+            })
     }
 
+    /// Problems are separated by columns which are blank in every line, so lines may be ragged
+    /// or have their trailing whitespace trimmed. Tabs always separate problems, the sections
+    /// between them are padded to their widest line and laid out beside each other with one blank
+    /// column in between, which the reported columns refer to.
+    ///
+    /// The problem kind may be followed by an annotation overriding `kind` for that problem,
+    /// see [`ParseKind::try_from`].
     fn parse(s: &str, kind: ParseKind) -> Result<Self, ParseWorksheetError> {
-        let mut lines_iter = s.lines().filter(|line| !line.is_empty());
-
-        let problem_kind_line = lines_iter.next_back().ok_or(ParseWorksheetError::Empty)?;
-        let mut problem_kinds: Vec<ProblemKind> = Vec::new();
-        let mut column_widths = Vec::new();
-
-        let mut current_index = 0;
-        while let Some(next_kind_index) =
-            problem_kind_line
-                .get((current_index + 1)..)
-                .and_then(|slice| {
-                    slice
-                        .find(|c: char| !c.is_whitespace())
-                        .map(|index| index + current_index + 1)
-                })
-        {
-            problem_kinds.push(
-                problem_kind_line[current_index..next_kind_index]
-                    .trim()
-                    .parse()?,
-            );
-            column_widths.push(next_kind_index - current_index - 1);
-            current_index = next_kind_index;
+        let sectioned_lines = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split('\t')
+                    .map(|section| section.chars().collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut section_widths = Vec::new();
+        for sections in &sectioned_lines {
+            section_widths.resize(section_widths.len().max(sections.len()), 0);
+            for (section, width) in sections.iter().zip(&mut section_widths) {
+                *width = section.len().max(*width);
+            }
         }
-        problem_kinds.push(problem_kind_line[current_index..].trim().parse()?);
-        column_widths.push(problem_kind_line.len() - current_index);
-
-        let mut input_cells: Vec<Vec<&str>> = Vec::with_capacity(problem_kinds.len());
-        for line in lines_iter {
-            let mut line_offset = 0;
-            for (column_index, column_width) in column_widths.iter().enumerate() {
-                let cell = &line[line_offset..][..*column_width];
-                match input_cells.get_mut(column_index) {
-                    Some(problem) => problem.push(cell),
-                    None => input_cells.push(vec![cell]),
+        let mut lines = sectioned_lines
+            .into_iter()
+            .map(|sections| {
+                let mut line = Vec::new();
+                for (index, &section_width) in section_widths.iter().enumerate() {
+                    if index > 0 {
+                        line.push(' ');
+                    }
+                    let section = sections.get(index).map_or(&[][..], Vec::as_slice);
+                    line.extend_from_slice(section);
+                    line.resize(line.len() + section_width - section.len(), ' ');
                 }
-                line_offset += column_width + 1;
+                line
+            })
+            .collect::<Vec<_>>();
+        let width = lines.first().map_or(0, Vec::len);
+        let problem_kind_line = lines.pop().ok_or(ParseWorksheetError::Empty)?;
+        let is_blank_column = |column_index: usize| {
+            problem_kind_line[column_index].is_whitespace()
+                && lines.iter().all(|line| line[column_index].is_whitespace())
+        };
+
+        let mut problems = Vec::new();
+        let mut column_index = 0;
+        while column_index < width {
+            if is_blank_column(column_index) {
+                column_index += 1;
+                continue;
+            }
+            let columns_start = column_index;
+            while column_index < width && !is_blank_column(column_index) {
+                column_index += 1;
             }
+            let columns = columns_start..column_index;
+            let mut problem_kind_and_annotation = problem_kind_line[columns.clone()]
+                .iter()
+                .filter(|c| !c.is_whitespace());
+            let problem_kind = problem_kind_and_annotation
+                .next()
+                .ok_or_else(|| ParseWorksheetError::MissingProblemKind {
+                    columns: columns.clone(),
+                })?
+                .to_string()
+                .parse()?;
+            let problem_parse_kind = match (
+                problem_kind_and_annotation.next(),
+                problem_kind_and_annotation.next(),
+            ) {
+                (None, _) => kind,
+                (Some(&annotation), None) => ParseKind::try_from(annotation)?,
+                (Some(_), Some(_)) => {
+                    return Err(ParseWorksheetError::MultipleAnnotations { columns });
+                }
+            };
+            let cells = CellMatrix::new(lines.iter().map(|line| &line[columns.clone()]));
+            problems.push(Problem {
+                numbers: problem_parse_kind
+                    .read(&cells)
+                    .iter()
+                    .map(|number| number.trim())
+                    .filter(|number| !number.is_empty())
+                    .map(|number| number.parse().map_err(ParseWorksheetError::ParseNumber))
+                    .collect::<Result<_, _>>()?,
+                kind: problem_kind,
+            });
         }
+        Ok(Self(problems.into_boxed_slice()))
+    }
 
-        Ok(Self(
-            problem_kinds
-                .into_iter()
-                .zip(input_cells.into_iter())
-                .map(|(problem_kind, cells)| Ok::<_, ParseWorksheetError>(Problem {
-                    numbers: match kind {
-                        ParseKind::TopToBottom => cells
-                            .into_iter()
-                            .map(|cell| cell.trim().parse()
-                                .map_err(ParseWorksheetError::ParseNumber))
-                            .collect::<Result<_, _>>()?,
-                        ParseKind::RightToLeft => {
-                            (0..cells.first().expect("Should have at least one cell").len())
-                                .rev()
-                                .map(|column_index| {
-                                    cells
-                                        .iter()
-                                        .map(|cell| cell.chars().nth(column_index).expect("Should have at least same amount of chars as first cell"))
-                                        .collect::<String>()
-                                        .trim()
-                                        .parse()
-                                        .map_err(ParseWorksheetError::ParseNumber)
-                                })
-                                .collect::<Result<_, _>>()?
-                        }
-                    },
-                    kind: problem_kind,
-                }))
-                .collect::<Result<_, _>>()?,
-        ))
+    /// Renders the worksheet column-aligned, so that parsing it with the same `kind` returns an
+    /// equal worksheet.
+    fn render(&self, kind: ParseKind) -> Result<String, RenderWorksheetError> {
+        let problems_cells = self
+            .0
+            .iter()
+            .map(|problem| problem.cells(kind))
+            .collect::<Result<Vec<_>, _>>()?;
+        let row_count = problems_cells
+            .iter()
+            .map(CellMatrix::row_count)
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![String::new(); row_count + 1];
+        for (index, (problem, cells)) in self.0.iter().zip(&problems_cells).enumerate() {
+            if index > 0 {
+                for line in &mut lines {
+                    line.push(' ');
+                }
+            }
+            let width = cells.columns.max(1);
+            let mut rows = cells.rows();
+            for line in &mut lines[..row_count] {
+                match rows.next() {
+                    Some(row) => line.extend(row),
+                    None => line.extend(std::iter::repeat_n(' ', width)),
+                }
+            }
+            lines[row_count].push_str(&format!("{:<width$}", problem.kind.to_string()));
+        }
+        Ok(lines.join("\n"))
     }
 }
 
+impl Display for Worksheet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rendered = self
+            .render(ParseKind::TopToBottom)
+            .map_err(|_| std::fmt::Error)?;
+        write!(f, "{rendered}")
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum RenderWorksheetError {
+    #[error("Rendering is not supported for {kind:?}")]
+    UnsupportedParseKind { kind: ParseKind },
+}
+
+/// How the numbers of a problem are read from its cells.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
 enum ParseKind {
+    /// Every row is a number, from the top to the bottom row.
     #[default]
     TopToBottom,
-    #[allow(dead_code)]
+    /// Every row is a number, from the bottom to the top row.
+    BottomToTop,
+    /// Every column read top-down is a number, from the left to the right column. This is
+    /// `TopToBottom` of the transposed cells.
+    LeftToRight,
+    /// Every column read top-down is a number, from the right to the left column. This is
+    /// `BottomToTop` of the transposed cells.
     RightToLeft,
+    /// Every diagonal going down to the right is a number, from the bottom left to the top right.
+    Diagonal,
+    /// Every diagonal going down to the left is a number, from the top left to the bottom right.
+    AntiDiagonal,
+}
+
+impl ParseKind {
+    /// Transforms the cells so that every row is one number, from the top to the bottom row.
+    fn read(self, cells: &CellMatrix) -> Vec<String> {
+        let cells = match self {
+            ParseKind::TopToBottom => cells.clone(),
+            ParseKind::BottomToTop => cells.flipped_vertically(),
+            ParseKind::LeftToRight => cells.transposed(),
+            ParseKind::RightToLeft => cells.transposed().flipped_vertically(),
+            ParseKind::Diagonal => cells.sheared(Shear::Diagonal).transposed(),
+            ParseKind::AntiDiagonal => cells.sheared(Shear::AntiDiagonal).transposed(),
+        };
+        cells
+            .rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect()
+    }
+}
+
+/// Parses the annotation after a problem kind: `T`op to bottom, `B`ottom to top, `L`eft to right,
+/// `R`ight to left, `D`iagonal or `A`nti-diagonal.
+impl TryFrom<char> for ParseKind {
+    type Error = ParseKindAnnotationError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'T' => Ok(ParseKind::TopToBottom),
+            'B' => Ok(ParseKind::BottomToTop),
+            'L' => Ok(ParseKind::LeftToRight),
+            'R' => Ok(ParseKind::RightToLeft),
+            'D' => Ok(ParseKind::Diagonal),
+            'A' => Ok(ParseKind::AntiDiagonal),
+            _ => Err(ParseKindAnnotationError::Unknown { annotation: value }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParseKindAnnotationError {
+    #[error("Unknown annotation '{annotation}'")]
+    Unknown { annotation: char },
+}
+
+/// The cells of one problem without its problem kind line.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct CellMatrix {
+    columns: usize,
+    cells: Vec<char>,
+}
+
+impl CellMatrix {
+    fn new<'a>(rows: impl IntoIterator<Item = &'a [char]>) -> Self {
+        let mut columns = 0;
+        let mut cells = Vec::new();
+        for row in rows {
+            columns = row.len();
+            cells.extend_from_slice(row);
+        }
+        Self { columns, cells }
+    }
+
+    fn row_count(&self) -> usize {
+        self.cells.len().checked_div(self.columns).unwrap_or(0)
+    }
+
+    fn rows(&self) -> impl DoubleEndedIterator<Item = &[char]> {
+        self.cells.chunks(self.columns.max(1))
+    }
+
+    fn transposed(&self) -> Self {
+        let row_count = self.row_count();
+        Self {
+            columns: row_count,
+            cells: (0..self.columns)
+                .flat_map(|column| {
+                    (0..row_count).map(move |row| self.cells[row * self.columns + column])
+                })
+                .collect(),
+        }
+    }
+
+    fn flipped_vertically(&self) -> Self {
+        Self::new(self.rows().rev())
+    }
+
+    /// Shifts every row, so that the diagonals become columns, filling up with blanks.
+    fn sheared(&self, shear: Shear) -> Self {
+        let row_count = self.row_count();
+        let columns = self.columns + row_count.saturating_sub(1);
+        let mut cells = vec![' '; row_count * columns];
+        for (row_index, row) in self.rows().enumerate() {
+            let shift = match shear {
+                Shear::Diagonal => row_count - 1 - row_index,
+                Shear::AntiDiagonal => row_index,
+            };
+            let start = row_index * columns + shift;
+            cells[start..start + self.columns].copy_from_slice(row);
+        }
+        Self { columns, cells }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Shear {
+    Diagonal,
+    AntiDiagonal,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -125,6 +345,12 @@ pub enum ParseWorksheetError {
     ParseNumber(#[from] ParseIntError),
     #[error("Failed to parse problem kind: {0}")]
     ParseKind(#[from] ParseProblemKindError),
+    #[error("Problem in columns {columns:?} has no problem kind")]
+    MissingProblemKind { columns: Range<usize> },
+    #[error("Problem in columns {columns:?} has more than one annotation")]
+    MultipleAnnotations { columns: Range<usize> },
+    #[error("Failed to parse annotation: {0}")]
+    ParseAnnotation(#[from] ParseKindAnnotationError),
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -134,28 +360,176 @@ struct Problem {
 }
 
 impl Problem {
-    fn solve(&self) -> u64 {
-        match self.kind {
-            ProblemKind::Add => self
-                .numbers
-                .iter()
-                .copied()
-                .reduce(|left, right| left.checked_add(right).expect("Should not overflow"))
-                .expect("Should not be empty"),
-            ProblemKind::Multiply => self
-                .numbers
-                .iter()
-                .copied()
-                .reduce(|left, right| left.checked_mul(right).expect("Should not overflow"))
-                .expect("Should not be empty"),
+    /// Powers are limited to this many bits, as a big number type would never overflow.
+    const MAXIMUM_POWER_BITS: u128 = 1 << 24;
+
+    /// Returns the cells which read with `kind` result in the numbers of this problem.
+    fn cells(&self, kind: ParseKind) -> Result<CellMatrix, RenderWorksheetError> {
+        let numbers = self.numbers.iter().map(u64::to_string).collect::<Vec<_>>();
+        let width = numbers.iter().map(String::len).max().unwrap_or(0);
+        let rows = numbers
+            .iter()
+            .map(|number| format!("{number:>width$}").chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let rows = CellMatrix::new(rows.iter().map(Vec::as_slice));
+        match kind {
+            ParseKind::TopToBottom => Ok(rows),
+            ParseKind::BottomToTop => Ok(rows.flipped_vertically()),
+            ParseKind::LeftToRight => Ok(rows.transposed()),
+            ParseKind::RightToLeft => Ok(rows.flipped_vertically().transposed()),
+            ParseKind::Diagonal | ParseKind::AntiDiagonal => {
+                Err(RenderWorksheetError::UnsupportedParseKind { kind })
+            }
         }
     }
+
+    /// Applies the operator from the first to the last number, so `-`, `/` and `^` are left
+    /// associative.
+    fn solve<N: Number>(&self) -> Result<N, SolveProblemError> {
+        let mut numbers = self.numbers.iter().map(|&number| N::from(number));
+        let first = numbers.next().ok_or(SolveProblemError::Empty)?;
+        numbers.try_fold(first, |left, right| {
+            match self.kind {
+                ProblemKind::Add => left.checked_add(&right),
+                ProblemKind::Subtract => left.checked_sub(&right),
+                ProblemKind::Multiply => left.checked_mul(&right),
+                ProblemKind::Divide => {
+                    if right.is_zero() {
+                        return Err(SolveProblemError::DivisionByZero);
+                    }
+                    left.checked_div(&right)
+                }
+                ProblemKind::Minimum => Some(left.min(right)),
+                ProblemKind::Maximum => Some(left.max(right)),
+                ProblemKind::Exponent => match right.to_usize() {
+                    Some(exponent) => {
+                        let minimum_bits =
+                            u128::from(left.bits().saturating_sub(1)) * exponent as u128;
+                        if minimum_bits > Self::MAXIMUM_POWER_BITS {
+                            return Err(SolveProblemError::ExponentTooLarge { minimum_bits });
+                        }
+                        num_traits::checked_pow(left, exponent)
+                    }
+                    None => None,
+                },
+            }
+            .ok_or(SolveProblemError::Overflow { kind: self.kind })
+        })
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, number) in self.numbers.iter().enumerate() {
+            if index > 0 {
+                write!(f, " {} ", self.kind)?;
+            }
+            write!(f, "{number}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum SolveProblemError {
+    #[error("Problem has no numbers")]
+    Empty,
+    #[error("Problem overflowed while applying '{kind}'")]
+    Overflow { kind: ProblemKind },
+    #[error("Problem divides by zero")]
+    DivisionByZero,
+    #[error("Problem raises to a power with at least {minimum_bits} bits")]
+    ExponentTooLarge { minimum_bits: u128 },
+}
+
+#[derive(thiserror::Error, Debug)]
+enum SolveWorksheetError {
+    #[error("Failed to solve problem {index}: {source}")]
+    Problem {
+        index: usize,
+        source: SolveProblemError,
+    },
+    #[error("Grand total overflowed when adding problem {index}")]
+    GrandTotalOverflow { index: usize },
+}
+
+/// The operations needed to solve problems in every [`NumberType`].
+trait Number:
+    Clone
+    + Ord
+    + From<u64>
+    + Zero
+    + One
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + CheckedDiv
+    + ToPrimitive
+    + Bits
+{
+}
+
+trait Bits {
+    /// Returns the count of bits without leading zeros.
+    fn bits(&self) -> u64;
+}
+
+impl Bits for u64 {
+    fn bits(&self) -> u64 {
+        u64::from(u64::BITS - self.leading_zeros())
+    }
+}
+
+impl Bits for u128 {
+    fn bits(&self) -> u64 {
+        u64::from(u128::BITS - self.leading_zeros())
+    }
+}
+
+impl Bits for BigUint {
+    fn bits(&self) -> u64 {
+        BigUint::bits(self)
+    }
+}
+
+impl<N> Number for N where
+    N: Clone
+        + Ord
+        + From<u64>
+        + Zero
+        + One
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + ToPrimitive
+        + Bits
+{
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 enum ProblemKind {
     Add,
+    Subtract,
     Multiply,
+    Divide,
+    Minimum,
+    Maximum,
+    Exponent,
+}
+
+impl Display for ProblemKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProblemKind::Add => write!(f, "+"),
+            ProblemKind::Subtract => write!(f, "-"),
+            ProblemKind::Multiply => write!(f, "*"),
+            ProblemKind::Divide => write!(f, "/"),
+            ProblemKind::Minimum => write!(f, "<"),
+            ProblemKind::Maximum => write!(f, ">"),
+            ProblemKind::Exponent => write!(f, "^"),
+        }
+    }
 }
 
 impl FromStr for ProblemKind {
@@ -164,7 +538,12 @@ impl FromStr for ProblemKind {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "+" => Ok(ProblemKind::Add),
+            "-" => Ok(ProblemKind::Subtract),
             "*" => Ok(ProblemKind::Multiply),
+            "/" => Ok(ProblemKind::Divide),
+            "<" => Ok(ProblemKind::Minimum),
+            ">" => Ok(ProblemKind::Maximum),
+            "^" => Ok(ProblemKind::Exponent),
             _ => Err(ParseProblemKindError::Unknown),
         }
     }
@@ -186,10 +565,10 @@ mod tests {
         let input = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
 
         // Act
-        let part1 = part1(input);
+        let part1 = part1(input, NumberType::default());
 
         // Assert
-        assert_eq!(part1, 4277556);
+        assert_eq!(part1, AnyNumber::U64(4277556));
     }
 
     #[test]
@@ -198,9 +577,191 @@ mod tests {
         let input = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
 
         // Act
-        let part2 = part2(input);
+        let part2 = part2(input, NumberType::default());
+
+        // Assert
+        assert_eq!(part2, AnyNumber::U64(3263827));
+    }
+
+    #[test]
+    fn test_operators_and_overflow() {
+        // Arrange
+        let input = "100 7 9 3 2 4294967296\n 30 2 4 5 3 4294967296\n  5 1 7 1 2          1\n-   / < > ^ *         ";
+        let worksheet = Worksheet::parse(input, ParseKind::TopToBottom).expect("Should parse");
+
+        // Act
+        let solutions = worksheet
+            .0
+            .iter()
+            .map(|problem| problem.solve::<u128>().expect("Should solve"))
+            .collect::<Vec<_>>();
+        let overflow = worksheet.grand_total_as(NumberType::U64);
+        let big = worksheet.grand_total_as(NumberType::Big);
+
+        // Assert
+        assert_eq!(solutions, vec![65, 3, 4, 5, 64, 1 << 64]);
+        assert!(matches!(
+            overflow,
+            Err(SolveWorksheetError::Problem {
+                index: 5,
+                source: SolveProblemError::Overflow {
+                    kind: ProblemKind::Multiply
+                },
+            })
+        ));
+        assert_eq!(
+            big.expect("Should solve").to_string(),
+            ((1u128 << 64) + 65 + 3 + 4 + 5 + 64).to_string()
+        );
+    }
+
+    #[test]
+    fn test_exponent_too_large() {
+        // Arrange
+        let problem = |exponent| Problem {
+            numbers: vec![2, exponent],
+            kind: ProblemKind::Exponent,
+        };
+
+        // Act
+        let big = problem(1000).solve::<BigUint>();
+        let too_large = problem(1_000_000_000_000).solve::<BigUint>();
+
+        // Assert
+        assert_eq!(big.expect("Should solve"), BigUint::from(1u8) << 1000);
+        assert!(matches!(
+            too_large,
+            Err(SolveProblemError::ExponentTooLarge {
+                minimum_bits: 1_000_000_000_000
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_ragged_and_tab_separated() {
+        // Arrange
+        let ragged = "123 328  51 64\n 45 64  387 23\n  6 98  215 314\n*   +   *   +";
+        let tab_separated =
+            "123\t328\t 51\t64 \n 45\t64 \t387\t23 \n  6\t98 \t215\t314\n*  \t+  \t*  \t+  ";
+        let missing_kind = "1 2\n3 4\n+";
+        let multi_byte = "1 ä\n+ +";
+
+        // Act
+        let ragged = Worksheet::parse(ragged, ParseKind::RightToLeft);
+        let tab_separated = Worksheet::parse(tab_separated, ParseKind::RightToLeft);
+        let missing_kind = Worksheet::parse(missing_kind, ParseKind::TopToBottom);
+        let multi_byte = Worksheet::parse(multi_byte, ParseKind::TopToBottom);
+
+        // Assert
+        let expected = Worksheet::parse(
+            "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ",
+            ParseKind::RightToLeft,
+        )
+        .expect("Should parse");
+        assert_eq!(ragged.expect("Should parse"), expected);
+        assert_eq!(tab_separated.expect("Should parse"), expected);
+        assert!(matches!(
+            missing_kind,
+            Err(ParseWorksheetError::MissingProblemKind { columns }) if columns == (2..3)
+        ));
+        assert!(matches!(
+            multi_byte,
+            Err(ParseWorksheetError::ParseNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_unaligned_tab_separated() {
+        // Arrange
+        let inputs = [
+            "1\t2\n10\t20\n+\t*",
+            "12\t3\n4\t56\n+\t*",
+            "123456789\t1\n1\t1\n+\t*",
+        ];
+
+        // Act
+        let worksheets = inputs.map(|input| {
+            Worksheet::parse(input, ParseKind::TopToBottom)
+                .expect("Should parse")
+                .grand_total::<u64>()
+                .expect("Should solve")
+        });
 
         // Assert
-        assert_eq!(part2, 3263827);
+        assert_eq!(worksheets, [11 + 40, 16 + 168, 123456790 + 1]);
+    }
+
+    #[test]
+    fn test_reading_orders() {
+        // Arrange
+        let input = "12\n34\n+";
+        let annotated = "12 12 12 12 12 12\n34 34 34 34 34 34\n+T +B +L +R +D +A";
+
+        // Act
+        let numbers = [
+            ParseKind::TopToBottom,
+            ParseKind::BottomToTop,
+            ParseKind::LeftToRight,
+            ParseKind::RightToLeft,
+            ParseKind::Diagonal,
+            ParseKind::AntiDiagonal,
+        ]
+        .map(|kind| {
+            Worksheet::parse(input, kind).expect("Should parse").0[0]
+                .numbers
+                .clone()
+        });
+        let annotated = Worksheet::parse(annotated, ParseKind::TopToBottom).expect("Should parse");
+
+        // Assert
+        let expected = [
+            vec![12, 34],
+            vec![34, 12],
+            vec![13, 24],
+            vec![24, 13],
+            vec![3, 14, 2],
+            vec![1, 23, 4],
+        ];
+        assert_eq!(numbers, expected);
+        assert_eq!(
+            annotated
+                .0
+                .iter()
+                .map(|problem| problem.numbers.clone())
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        // Arrange
+        let input = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+        let kinds = [
+            ParseKind::TopToBottom,
+            ParseKind::BottomToTop,
+            ParseKind::LeftToRight,
+            ParseKind::RightToLeft,
+        ];
+
+        for kind in kinds {
+            let worksheet = Worksheet::parse(input, kind).expect("Should parse");
+
+            // Act
+            let rendered = worksheet.render(kind).expect("Should render");
+
+            // Assert
+            assert_eq!(
+                Worksheet::parse(&rendered, kind).expect("Should parse rendered"),
+                worksheet
+            );
+        }
+        let worksheet = Worksheet::parse(input, ParseKind::TopToBottom).expect("Should parse");
+        assert_eq!(
+            worksheet.to_string(),
+            "123 328  51  64\n 45  64 387  23\n  6  98 215 314\n*   +   *   +  "
+        );
+        assert_eq!(worksheet.0[0].to_string(), "123 * 45 * 6");
+        assert!(worksheet.render(ParseKind::Diagonal).is_err());
     }
 }
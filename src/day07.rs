@@ -1,34 +1,67 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::iter::once;
+#[cfg(feature = "part1")]
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+#[cfg(feature = "part2")]
+use aoc_2025::number_type::{AnyNumber, NumberType};
+use num_traits::{CheckedAdd, One, Zero};
 
 fn main() {
-    aoc_2025::aoc!(INPUT, part1, part2);
+    let beam_rules = beam_rules();
+    #[cfg(feature = "part2")]
+    let number_type = number_type();
+    aoc_2025::aoc!(INPUT, part1(beam_rules), part2(beam_rules, number_type));
 }
 
 const INPUT: &str = include_str!("../input/input.day07");
 
 #[cfg(feature = "part1")]
-fn part1(input: &str) -> u64 {
+fn part1(input: &str, beam_rules: BeamRules) -> u64 {
     // Took 34 minutes 35,81 seconds
     TachyonManifold::parse(input)
         .expect("Should parse")
-        .run_tachyon_beam()
+        .run_tachyon_beam::<Uncounted>(beam_rules)
+        .expect("Should run tachyon beam")
         .split_count()
 }
 
 #[cfg(feature = "part2")]
-fn part2(input: &str) -> u64 {
+fn part2(input: &str, beam_rules: BeamRules, number_type: NumberType) -> AnyNumber {
     // Took 19 minutes 4,20 seconds
     TachyonManifold::parse(input)
         .expect("Should parse")
-        .run_tachyon_beam()
-        .timelines_count()
+        .timelines_count_as(beam_rules, number_type)
+        .expect("Should run tachyon beam")
+}
+
+#[cfg(feature = "part2")]
+fn number_type() -> NumberType {
+    aoc_2025::option("counts")
+        .map(|number_type| number_type.parse().expect("Should parse number type"))
+        .unwrap_or_default()
+}
+
+fn beam_rules() -> BeamRules {
+    BeamRules {
+        adjacent_splitters: aoc_2025::option("adjacent-splitters")
+            .map(|rule| rule.parse().expect("Should parse adjacent splitters rule"))
+            .unwrap_or_default(),
+        edges: aoc_2025::option("edges")
+            .map(|rule| rule.parse().expect("Should parse edges rule"))
+            .unwrap_or_default(),
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 struct TachyonManifold {
     area: Vec<Vec<Field>>,
-    split_count: u64,
+    /// The positions of the starts, ordered by row and column.
+    starts: Vec<(usize, usize)>,
+    /// Whether there are mirrors or one-way splitters, which can turn beams away from downwards.
+    has_optical_elements: bool,
 }
 
 impl TachyonManifold {
@@ -37,122 +70,541 @@ impl TachyonManifold {
         let Some(first_line) = lines_iter.next() else {
             return Ok(Self {
                 area: Vec::new(),
-                split_count: 0,
+                starts: Vec::new(),
+                has_optical_elements: false,
             });
         };
         let expected_columns = first_line.len();
+        let area = once(first_line)
+            .chain(lines_iter)
+            .map(|line| {
+                let line = line
+                    .chars()
+                    .map(|field| match field {
+                        '.' => Ok(Field::EmptySpace),
+                        '^' => Ok(Field::Splitter),
+                        'S' => Ok(Field::Start),
+                        '/' => Ok(Field::Mirror(Mirror::Slash)),
+                        '\\' => Ok(Field::Mirror(Mirror::Backslash)),
+                        '#' => Ok(Field::Absorber),
+                        '<' => Ok(Field::OneWaySplitter(Direction::Left)),
+                        '>' => Ok(Field::OneWaySplitter(Direction::Right)),
+                        _ => Err(ParseTachyonManifoldError::UnknownField { field }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if line.len() != expected_columns {
+                    Err(ParseTachyonManifoldError::UnexpectedColumnCount { count: line.len() })
+                } else {
+                    Ok(line)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut starts = Vec::new();
+        let mut has_optical_elements = false;
+        for (row, fields) in area.iter().enumerate() {
+            for (column, field) in fields.iter().enumerate() {
+                match field {
+                    Field::Start => starts.push((row, column)),
+                    Field::Mirror(_) | Field::OneWaySplitter(_) => has_optical_elements = true,
+                    _ => {}
+                }
+            }
+        }
         Ok(Self {
-            area: once(first_line)
-                .chain(lines_iter)
-                .map(|line| {
-                    let line = line
-                        .chars()
-                        .map(|field| match field {
-                            '.' => Ok(Field::EmptySpace),
-                            '^' => Ok(Field::Splitter),
-                            'S' => Ok(Field::Start),
-                            _ => Err(ParseTachyonManifoldError::UnknownField { field }),
-                        })
-                        .collect::<Result<Vec<_>, _>>()?;
-                    if line.len() != expected_columns {
-                        Err(ParseTachyonManifoldError::UnexpectedColumnCount { count: line.len() })
-                    } else {
-                        Ok(line)
-                    }
-                })
-                .collect::<Result<Vec<_>, _>>()?,
-            split_count: 0,
+            area,
+            starts,
+            has_optical_elements,
         })
     }
-    fn run_tachyon_beam(&mut self) -> &mut Self {
-        for row_index in 0..self.area.len().saturating_sub(1) {
-            for column_index in 0..self.area[row_index].len() {
-                let beam_to_be_propagated_count = match self.area[row_index][column_index] {
-                    Field::EmptySpace | Field::Splitter => 0,
-                    Field::Start => 1,
-                    Field::Beam { count_in_timelines } => count_in_timelines,
-                };
-                if beam_to_be_propagated_count > 0 {
-                    match self.area[row_index + 1][column_index] {
-                        Field::Start => unreachable!("There will be no start besides first line"),
-                        Field::EmptySpace => {
-                            self.area[row_index + 1][column_index] = Field::Beam {
-                                count_in_timelines: beam_to_be_propagated_count,
-                            }
-                        }
-                        Field::Splitter => {
-                            self.split_count += 1;
-                            if column_index > 0 {
-                                match self.area[row_index + 1][column_index - 1] {
-                                    Field::Start => {
-                                        unreachable!("There will be no start besides first line")
-                                    }
-                                    Field::EmptySpace => {
-                                        self.area[row_index + 1][column_index - 1] = Field::Beam {
-                                            count_in_timelines: beam_to_be_propagated_count,
-                                        }
-                                    }
-                                    Field::Splitter => unimplemented!("Do not know what to do now"),
-                                    Field::Beam { count_in_timelines } => {
-                                        self.area[row_index + 1][column_index - 1] = Field::Beam {
-                                            count_in_timelines: count_in_timelines
-                                                + beam_to_be_propagated_count,
-                                        }
-                                    }
-                                }
-                            }
-                            if column_index < self.area[row_index].len().saturating_sub(1) {
-                                match self.area[row_index + 1][column_index + 1] {
-                                    Field::Start => {
-                                        unreachable!("There will be no start besides first line")
-                                    }
-                                    Field::EmptySpace => {
-                                        self.area[row_index + 1][column_index + 1] = Field::Beam {
-                                            count_in_timelines: beam_to_be_propagated_count,
-                                        }
-                                    }
-                                    Field::Splitter => unimplemented!("Do not know what to do now"),
-                                    Field::Beam { count_in_timelines } => {
-                                        self.area[row_index + 1][column_index + 1] = Field::Beam {
-                                            count_in_timelines: count_in_timelines
-                                                + beam_to_be_propagated_count,
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        Field::Beam { count_in_timelines } => {
-                            self.area[row_index + 1][column_index] = Field::Beam {
-                                count_in_timelines: count_in_timelines
-                                    + beam_to_be_propagated_count,
+
+    #[cfg(feature = "part2")]
+    fn timelines_count_as(
+        &self,
+        rules: BeamRules,
+        number_type: NumberType,
+    ) -> Result<AnyNumber, RunTachyonBeamError> {
+        Ok(match number_type {
+            NumberType::U64 => AnyNumber::U64(self.run_tachyon_beam(rules)?.timelines_count()),
+            NumberType::U128 => AnyNumber::U128(self.run_tachyon_beam(rules)?.timelines_count()),
+            NumberType::Big => AnyNumber::Big(self.run_tachyon_beam(rules)?.timelines_count()),
+        })
+    }
+
+    /// Counts the timelines in `C`, failing instead of overflowing silently. Beams only travel
+    /// downwards unless the manifold contains mirrors or one-way splitters, so only those
+    /// manifolds need to be traced per beam direction.
+    fn run_tachyon_beam<C: Count>(
+        &self,
+        rules: BeamRules,
+    ) -> Result<TachyonBeamRun<C>, RunTachyonBeamError> {
+        if self.has_optical_elements {
+            self.trace_tachyon_beam(rules)
+        } else {
+            self.propagate_tachyon_beam(rules)
+        }
+    }
+
+    /// Propagates the beams row by row, only visiting the columns which currently carry a beam.
+    fn propagate_tachyon_beam<C: Count>(
+        &self,
+        rules: BeamRules,
+    ) -> Result<TachyonBeamRun<C>, RunTachyonBeamError> {
+        let mut split_count = 0;
+        let mut activated_columns = Vec::new();
+        let mut beams = BTreeMap::new();
+        let mut starts = self.starts.iter().peekable();
+        for (row, fields) in self.area.iter().enumerate() {
+            while let Some((_, column)) = starts.next_if(|(start_row, _)| *start_row == row) {
+                add_timelines(&mut beams, row, *column, &C::one())?;
+            }
+            let mut next_beams = BTreeMap::new();
+            for (column, timelines) in beams {
+                match fields[column] {
+                    Field::Start | Field::EmptySpace => {
+                        add_timelines(&mut next_beams, row + 1, column, &timelines)?;
+                    }
+                    Field::Absorber => {}
+                    Field::Splitter => {
+                        activated_columns.push(column);
+                        for side in Direction::Down.sides() {
+                            let Some((_, split_column)) =
+                                self.split_beam((row, column), side, rules, &mut |(_, column)| {
+                                    activated_columns.push(column)
+                                })?
+                            else {
+                                continue;
+                            };
+                            if fields[split_column] != Field::Absorber {
+                                add_timelines(&mut next_beams, row + 1, split_column, &timelines)?;
                             }
                         }
                     }
+                    Field::Mirror(_) | Field::OneWaySplitter(_) => {
+                        unreachable!("Should trace manifolds with optical elements")
+                    }
+                }
+            }
+            // Cascading split beams can activate a splitter a beam also reaches directly.
+            activated_columns.sort_unstable();
+            activated_columns.dedup();
+            split_count += activated_columns.len() as u64;
+            activated_columns.clear();
+            beams = next_beams;
+        }
+        let timelines_count =
+            beams
+                .into_iter()
+                .try_fold(C::zero(), |total, (column, timelines)| {
+                    total.checked_add(&timelines).ok_or(
+                        RunTachyonBeamError::TimelinesCountOverflow {
+                            row: self.area.len(),
+                            column,
+                        },
+                    )
+                })?;
+        Ok(TachyonBeamRun {
+            split_count,
+            timelines_count,
+        })
+    }
+
+    /// Follows every beam from every start through the manifold, counting the timelines by
+    /// memoising them per beam position and direction, so merged beams are only followed once.
+    fn trace_tachyon_beam<C: Count>(
+        &self,
+        rules: BeamRules,
+    ) -> Result<TachyonBeamRun<C>, RunTachyonBeamError> {
+        let mut activated_splitters = HashSet::new();
+        let mut timelines_per_beam = HashMap::new();
+        let mut timelines_count = C::zero();
+        for &(row, column) in &self.starts {
+            let start = Beam {
+                position: (row, column),
+                direction: Direction::Down,
+            };
+            let timelines = self.count_timelines(
+                start,
+                rules,
+                &mut activated_splitters,
+                &mut timelines_per_beam,
+            )?;
+            timelines_count = timelines_count
+                .checked_add(&timelines)
+                .ok_or(RunTachyonBeamError::TimelinesCountOverflow { row, column })?;
+        }
+        Ok(TachyonBeamRun {
+            split_count: activated_splitters.len() as u64,
+            timelines_count,
+        })
+    }
+
+    /// Depth first search without recursion, as beams can travel through the whole manifold.
+    fn count_timelines<C: Count>(
+        &self,
+        start: Beam,
+        rules: BeamRules,
+        activated_splitters: &mut HashSet<(usize, usize)>,
+        timelines_per_beam: &mut HashMap<Beam, C>,
+    ) -> Result<C, RunTachyonBeamError> {
+        if let Some(timelines) = timelines_per_beam.get(&start) {
+            return Ok(timelines.clone());
+        }
+        let mut following = HashSet::from([start]);
+        let mut stack = vec![(
+            start,
+            self.step(start, rules, activated_splitters)?.into_iter(),
+            C::zero(),
+        )];
+        while let Some((beam, steps, timelines)) = stack.last_mut() {
+            let overflow = RunTachyonBeamError::TimelinesCountOverflow {
+                row: beam.position.0,
+                column: beam.position.1,
+            };
+            match steps.next() {
+                Some(Step::Exit) => {
+                    *timelines = timelines.checked_add(&C::one()).ok_or(overflow)?;
+                }
+                Some(Step::Beam(next_beam)) => {
+                    if let Some(next_timelines) = timelines_per_beam.get(&next_beam) {
+                        *timelines = timelines.checked_add(next_timelines).ok_or(overflow)?;
+                    } else if !following.insert(next_beam) {
+                        return Err(RunTachyonBeamError::Cycle {
+                            row: next_beam.position.0,
+                            column: next_beam.position.1,
+                        });
+                    } else {
+                        let next_steps = self.step(next_beam, rules, activated_splitters)?;
+                        stack.push((next_beam, next_steps.into_iter(), C::zero()));
+                    }
+                }
+                None => {
+                    let (beam, _, timelines) = stack.pop().expect("Should have current beam");
+                    following.remove(&beam);
+                    if let Some((_, _, parent_timelines)) = stack.last_mut() {
+                        *parent_timelines =
+                            parent_timelines.checked_add(&timelines).ok_or(overflow)?;
+                    }
+                    timelines_per_beam.insert(beam, timelines.clone());
+                    if stack.is_empty() {
+                        return Ok(timelines);
+                    }
                 }
             }
         }
-        self
+        unreachable!("Should have returned when the start beam was finished")
     }
 
+    /// Lets the field below the beam act on it and returns where the beam goes next.
+    fn step(
+        &self,
+        beam: Beam,
+        rules: BeamRules,
+        activated_splitters: &mut HashSet<(usize, usize)>,
+    ) -> Result<Vec<Step>, RunTachyonBeamError> {
+        let Beam {
+            position,
+            direction,
+        } = beam;
+        let sides = match self.area[position.0][position.1] {
+            Field::Start | Field::EmptySpace => return Ok(vec![self.forward(position, direction)]),
+            Field::Absorber => return Ok(Vec::new()),
+            Field::Mirror(mirror) => {
+                return Ok(vec![self.forward(position, direction.reflected(mirror))]);
+            }
+            Field::Splitter => direction.sides().to_vec(),
+            Field::OneWaySplitter(side) if direction.is_vertical() => vec![side],
+            Field::OneWaySplitter(_) => return Ok(vec![self.forward(position, direction)]),
+        };
+        activated_splitters.insert(position);
+        let mut steps = Vec::new();
+        for side in sides {
+            if let Some(split_position) =
+                self.split_beam(position, side, rules, &mut |splitter| {
+                    activated_splitters.insert(splitter);
+                })?
+            {
+                steps.push(Step::Beam(Beam {
+                    position: split_position,
+                    direction,
+                }));
+            }
+        }
+        Ok(steps)
+    }
+
+    /// Moves a split beam to the side until it leaves the splitters, returning where it lands.
+    fn split_beam(
+        &self,
+        position: (usize, usize),
+        side: Direction,
+        rules: BeamRules,
+        activate_splitter: &mut impl FnMut((usize, usize)),
+    ) -> Result<Option<(usize, usize)>, RunTachyonBeamError> {
+        let mut split_position = position;
+        loop {
+            let Some(next_position) = self.moved(split_position, side) else {
+                return match rules.edges {
+                    EdgesRule::Absorb => Ok(None),
+                    EdgesRule::Error => Err(RunTachyonBeamError::BeamLeavesManifold {
+                        row: split_position.0,
+                        column: split_position.1,
+                    }),
+                };
+            };
+            split_position = next_position;
+            match self.area[split_position.0][split_position.1] {
+                Field::Splitter | Field::OneWaySplitter(_) => match rules.adjacent_splitters {
+                    AdjacentSplittersRule::Cascade => activate_splitter(split_position),
+                    AdjacentSplittersRule::Absorb => return Ok(None),
+                    AdjacentSplittersRule::Error => {
+                        return Err(RunTachyonBeamError::AdjacentSplitter {
+                            row: split_position.0,
+                            column: split_position.1,
+                        });
+                    }
+                },
+                _ => return Ok(Some(split_position)),
+            }
+        }
+    }
+
+    fn forward(&self, position: (usize, usize), direction: Direction) -> Step {
+        match self.moved(position, direction) {
+            Some(position) => Step::Beam(Beam {
+                position,
+                direction,
+            }),
+            None => Step::Exit,
+        }
+    }
+
+    fn moved(&self, (row, column): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (row_offset, column_offset) = direction.offset();
+        let row = row.checked_add_signed(row_offset)?;
+        let column = column.checked_add_signed(column_offset)?;
+        self.area
+            .get(row)
+            .filter(|fields| column < fields.len())
+            .map(|_| (row, column))
+    }
+}
+
+fn add_timelines<C: Count>(
+    beams: &mut BTreeMap<usize, C>,
+    row: usize,
+    column: usize,
+    timelines: &C,
+) -> Result<(), RunTachyonBeamError> {
+    let beam = beams.entry(column).or_insert_with(C::zero);
+    *beam = beam
+        .checked_add(timelines)
+        .ok_or(RunTachyonBeamError::TimelinesCountOverflow { row, column })?;
+    Ok(())
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct Beam {
+    position: (usize, usize),
+    direction: Direction,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Step {
+    Beam(Beam),
+    /// The beam left the manifold, which ends one timeline.
+    Exit,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// The directions in which a splitter emits the split beams.
+    fn sides(self) -> [Direction; 2] {
+        if self.is_vertical() {
+            [Direction::Left, Direction::Right]
+        } else {
+            [Direction::Up, Direction::Down]
+        }
+    }
+
+    fn reflected(self, mirror: Mirror) -> Self {
+        match (mirror, self) {
+            (Mirror::Slash, Direction::Up) | (Mirror::Backslash, Direction::Down) => {
+                Direction::Right
+            }
+            (Mirror::Slash, Direction::Down) | (Mirror::Backslash, Direction::Up) => {
+                Direction::Left
+            }
+            (Mirror::Slash, Direction::Left) | (Mirror::Backslash, Direction::Right) => {
+                Direction::Down
+            }
+            (Mirror::Slash, Direction::Right) | (Mirror::Backslash, Direction::Left) => {
+                Direction::Up
+            }
+        }
+    }
+}
+
+/// The operations needed to count timelines in every [`NumberType`].
+trait Count: Clone + Zero + One + CheckedAdd {}
+
+impl<C> Count for C where C: Clone + Zero + One + CheckedAdd {}
+
+/// Skips counting timelines when only the splits are needed, so the run cannot overflow.
+#[cfg(feature = "part1")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct Uncounted;
+
+#[cfg(feature = "part1")]
+impl Add for Uncounted {
+    type Output = Self;
+
+    fn add(self, _: Self) -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "part1")]
+impl Mul for Uncounted {
+    type Output = Self;
+
+    fn mul(self, _: Self) -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "part1")]
+impl Zero for Uncounted {
+    fn zero() -> Self {
+        Self
+    }
+
+    fn is_zero(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "part1")]
+impl One for Uncounted {
+    fn one() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "part1")]
+impl CheckedAdd for Uncounted {
+    fn checked_add(&self, _: &Self) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+struct BeamRules {
+    adjacent_splitters: AdjacentSplittersRule,
+    edges: EdgesRule,
+}
+
+/// What happens to a split beam landing on a splitter right next to the one it was split by.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+enum AdjacentSplittersRule {
+    /// The splitter is hit and passes the beam on further to the side, which can cascade.
+    Cascade,
+    /// The splitter swallows the beam.
+    Absorb,
+    #[default]
+    Error,
+}
+
+impl FromStr for AdjacentSplittersRule {
+    type Err = ParseRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cascade" => Ok(Self::Cascade),
+            "absorb" => Ok(Self::Absorb),
+            "error" => Ok(Self::Error),
+            _ => Err(ParseRuleError::Unknown {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// What happens to a split beam leaving the manifold to the left or right.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+enum EdgesRule {
+    /// The beam is lost.
+    #[default]
+    Absorb,
+    Error,
+}
+
+impl FromStr for EdgesRule {
+    type Err = ParseRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "absorb" => Ok(Self::Absorb),
+            "error" => Ok(Self::Error),
+            _ => Err(ParseRuleError::Unknown {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum ParseRuleError {
+    #[error("Unknown rule '{value}'")]
+    Unknown { value: String },
+}
+
+#[derive(thiserror::Error, Debug)]
+enum RunTachyonBeamError {
+    #[error("Split beam lands on adjacent splitter in row {row} and column {column}")]
+    AdjacentSplitter { row: usize, column: usize },
+    #[error("Split beam leaves manifold in row {row} after column {column}")]
+    BeamLeavesManifold { row: usize, column: usize },
+    #[error("Timelines count overflowed in row {row} and column {column}")]
+    TimelinesCountOverflow { row: usize, column: usize },
+    #[error("Beam runs in a cycle through row {row} and column {column}")]
+    Cycle { row: usize, column: usize },
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct TachyonBeamRun<C> {
+    split_count: u64,
+    timelines_count: C,
+}
+
+impl<C> TachyonBeamRun<C> {
     #[cfg(feature = "part1")]
     fn split_count(&self) -> u64 {
         self.split_count
     }
 
     #[cfg(feature = "part2")]
-    fn timelines_count(&self) -> u64 {
-        let Some(last_row) = self.area.last() else {
-            return 0;
-        };
-        last_row
-            .iter()
-            .map(|row| match row {
-                Field::Start => 0,
-                Field::EmptySpace => 0,
-                Field::Splitter => 0,
-                Field::Beam { count_in_timelines } => *count_in_timelines,
-            })
-            .sum()
+    fn timelines_count(self) -> C {
+        self.timelines_count
     }
 }
 
@@ -164,12 +616,11 @@ impl Display for TachyonManifold {
                     Field::Start => write!(f, "S")?,
                     Field::EmptySpace => write!(f, ".")?,
                     Field::Splitter => write!(f, "^")?,
-                    Field::Beam { count_in_timelines } => match count_in_timelines {
-                        0 => unreachable!(),
-                        1 => write!(f, "|")?,
-                        c if (2..=9).contains(&c) => write!(f, "{}", c)?,
-                        _ => write!(f, "+")?,
-                    },
+                    Field::Mirror(Mirror::Slash) => write!(f, "/")?,
+                    Field::Mirror(Mirror::Backslash) => write!(f, "\\")?,
+                    Field::Absorber => write!(f, "#")?,
+                    Field::OneWaySplitter(Direction::Left) => write!(f, "<")?,
+                    Field::OneWaySplitter(_) => write!(f, ">")?,
                 }
             }
             writeln!(f)?;
@@ -180,10 +631,23 @@ impl Display for TachyonManifold {
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 enum Field {
+    /// Emits a beam downwards, other beams pass through.
     Start,
     EmptySpace,
+    /// Splits a beam into the fields beside it, perpendicular to the direction of the beam.
     Splitter,
-    Beam { count_in_timelines: u64 },
+    /// Splits vertical beams only into the field on the given side, horizontal beams pass through.
+    OneWaySplitter(Direction),
+    Mirror(Mirror),
+    Absorber,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Mirror {
+    /// `/`
+    Slash,
+    /// `\\`
+    Backslash,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -196,6 +660,8 @@ enum ParseTachyonManifoldError {
 
 #[cfg(test)]
 mod tests {
+    use num_bigint::BigUint;
+
     use super::*;
 
     #[test]
@@ -219,7 +685,7 @@ mod tests {
 ...............";
 
         // Act
-        let part1 = part1(input);
+        let part1 = part1(input, BeamRules::default());
 
         // Assert
         assert_eq!(part1, 21);
@@ -246,9 +712,193 @@ mod tests {
 ...............";
 
         // Act
-        let part2 = part2(input);
+        let part2 = part2(input, BeamRules::default(), NumberType::default());
+
+        // Assert
+        assert_eq!(part2, AnyNumber::U64(40));
+    }
+
+    #[test]
+    fn test_run_keeps_manifold_unchanged() {
+        // Arrange
+        let manifold = TachyonManifold::parse("..S..\n.....\n..^..\n.....\n.^...\n.....")
+            .expect("Should parse");
+        let before = manifold.clone();
+
+        // Act
+        let first_run = manifold.run_tachyon_beam::<u64>(BeamRules::default());
+        let second_run = manifold.run_tachyon_beam::<u64>(BeamRules::default());
+
+        // Assert
+        assert_eq!(manifold, before);
+        let first_run = first_run.expect("Should run");
+        assert_eq!(first_run, second_run.expect("Should run"));
+        assert_eq!(
+            first_run,
+            TachyonBeamRun {
+                split_count: 2,
+                timelines_count: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_adjacent_splitter_rules() {
+        // Arrange
+        let manifold =
+            TachyonManifold::parse("...S...\n.......\n..^^...\n.......").expect("Should parse");
+        let rules = |adjacent_splitters| BeamRules {
+            adjacent_splitters,
+            edges: EdgesRule::Absorb,
+        };
+
+        // Act
+        let cascade = manifold.run_tachyon_beam::<u64>(rules(AdjacentSplittersRule::Cascade));
+        let absorb = manifold.run_tachyon_beam::<u64>(rules(AdjacentSplittersRule::Absorb));
+        let error = manifold.run_tachyon_beam::<u64>(rules(AdjacentSplittersRule::Error));
+
+        // Assert
+        assert_eq!(
+            cascade.expect("Should run"),
+            TachyonBeamRun {
+                split_count: 2,
+                timelines_count: 2,
+            }
+        );
+        assert_eq!(
+            absorb.expect("Should run"),
+            TachyonBeamRun {
+                split_count: 1,
+                timelines_count: 1,
+            }
+        );
+        assert!(matches!(
+            error,
+            Err(RunTachyonBeamError::AdjacentSplitter { row: 2, column: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_edge_rules() {
+        // Arrange
+        let manifold = TachyonManifold::parse("S..\n...\n^..\n...").expect("Should parse");
+        let rules = |edges| BeamRules {
+            adjacent_splitters: AdjacentSplittersRule::Error,
+            edges,
+        };
+
+        // Act
+        let absorb = manifold.run_tachyon_beam::<u64>(rules(EdgesRule::Absorb));
+        let error = manifold.run_tachyon_beam::<u64>(rules(EdgesRule::Error));
+
+        // Assert
+        assert_eq!(
+            absorb.expect("Should run"),
+            TachyonBeamRun {
+                split_count: 1,
+                timelines_count: 1,
+            }
+        );
+        assert!(matches!(
+            error,
+            Err(RunTachyonBeamError::BeamLeavesManifold { row: 2, column: 0 })
+        ));
+    }
+
+    /// A start above `depth` levels of splitters, every level with one splitter more.
+    fn splitter_pyramid(depth: usize) -> String {
+        once(format!("{}S{}", ".".repeat(depth), ".".repeat(depth)))
+            .chain((0..depth).flat_map(|level| {
+                let splitters = (0..=level).map(|_| "^").collect::<Vec<_>>().join(".");
+                let padding = ".".repeat(depth - level);
+                [
+                    ".".repeat(2 * depth + 1),
+                    format!("{padding}{splitters}{padding}"),
+                ]
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_timelines_count_overflow() {
+        // Arrange
+        let depth = 66;
+        let manifold = TachyonManifold::parse(&splitter_pyramid(depth)).expect("Should parse");
+
+        // Act
+        let overflow = manifold.timelines_count_as(BeamRules::default(), NumberType::U64);
+        let big = manifold.timelines_count_as(BeamRules::default(), NumberType::Big);
+
+        // Assert
+        assert!(matches!(
+            overflow,
+            Err(RunTachyonBeamError::TimelinesCountOverflow { .. })
+        ));
+        assert_eq!(
+            big.expect("Should run"),
+            AnyNumber::Big(BigUint::from(1u8) << depth)
+        );
+    }
+
+    #[test]
+    fn test_part1_without_counting_timelines() {
+        // Arrange
+        let depth = 66;
+        let input = splitter_pyramid(depth);
+
+        // Act
+        let part1 = part1(&input, BeamRules::default());
+
+        // Assert
+        assert_eq!(part1, (depth * (depth + 1) / 2) as u64);
+    }
+
+    #[test]
+    fn test_propagation_matches_tracing() {
+        // Arrange
+        let manifold = TachyonManifold::parse(
+            ".......S.......\n.......^.......\n..S...^.^......\n..^..#.^^^.....\n.^.^...........",
+        )
+        .expect("Should parse");
+        let rules = BeamRules {
+            adjacent_splitters: AdjacentSplittersRule::Cascade,
+            edges: EdgesRule::Absorb,
+        };
+
+        // Act
+        let propagated = manifold.propagate_tachyon_beam::<u64>(rules);
+        let traced = manifold.trace_tachyon_beam::<u64>(rules);
+
+        // Assert
+        assert_eq!(
+            propagated.expect("Should propagate"),
+            traced.expect("Should trace")
+        );
+    }
+
+    #[test]
+    fn test_optical_elements() {
+        // Arrange
+        let manifold =
+            TachyonManifold::parse("S...S\n.....\n\\...<\n...#.\n.....").expect("Should parse");
+        let cycle = TachyonManifold::parse("/S\\\n.^.\n\\./").expect("Should parse");
+
+        // Act
+        let run = manifold.run_tachyon_beam::<u64>(BeamRules::default());
+        let cycle_run = cycle.run_tachyon_beam::<u64>(BeamRules::default());
 
         // Assert
-        assert_eq!(part2, 40);
+        assert_eq!(
+            run.expect("Should run"),
+            TachyonBeamRun {
+                split_count: 1,
+                timelines_count: 1,
+            }
+        );
+        assert!(matches!(
+            cycle_run,
+            Err(RunTachyonBeamError::Cycle { row: 1, column: 0 })
+        ));
     }
 }
@@ -1,142 +1,326 @@
-use std::collections::HashMap;
+#[cfg(feature = "part1")]
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::BufWriter;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
+#[cfg(feature = "part1")]
+use aoc_2025::disjoint_set::DisjointSet;
+#[cfg(feature = "part1")]
 use itertools::Itertools;
+use num_traits::{CheckedAdd, CheckedMul, One, Zero};
 
 fn main() {
-    aoc_2025::aoc!(INPUT, part1, part2);
+    let metric = metric();
+    for (option, format) in [
+        ("graph-dot", circuit_graph::GraphFormat::Dot),
+        ("graph-json", circuit_graph::GraphFormat::Json),
+    ] {
+        if let Some(path) = aoc_2025::option(option) {
+            let playground = Playground::from_str(INPUT).expect("Should parse");
+            let edges = match aoc_2025::option("graph")
+                .map(|edges| edges.parse().expect("Should parse graph edges"))
+                .unwrap_or_default()
+            {
+                circuit_graph::GraphEdges::Closest => {
+                    playground.closest_pairs(connect_count(), metric)
+                }
+                circuit_graph::GraphEdges::SpanningTree => playground.minimum_spanning_tree(metric),
+            };
+            let file = File::create(path).expect("Should create graph file");
+            circuit_graph::export(&playground, &edges, metric, format, BufWriter::new(file))
+                .expect("Should export graph");
+        }
+    }
+    #[cfg(feature = "part1")]
+    if let Some(connect_count) = aoc_2025::option("size-distribution") {
+        let playground = Playground::from_str(INPUT).expect("Should parse");
+        let connect_count = connect_count
+            .parse()
+            .expect("Should parse connection count");
+        for (size, count) in size_distribution(&playground, connect_count, metric) {
+            println!("{count} circuits of size {size}");
+        }
+    }
+    #[cfg(feature = "part1")]
+    let circuit_query = circuit_query();
+    #[cfg(feature = "part2")]
+    let reducer = reducer();
+    aoc_2025::aoc!(INPUT, part1(circuit_query, metric), part2(reducer, metric));
 }
 
 const INPUT: &str = include_str!("../input/input.day08");
 
 #[cfg(feature = "part1")]
-fn part1(input: &str) -> u64 {
+fn part1(input: &str, circuit_query: CircuitQuery, metric: Metric) -> u64 {
     // Took 1 hour 3 minutes 42,82 seconds
-    product_of_size_of_largets_circuits(Playground::from_str(input).expect("Should parse"), 1000)
+    reduce_sizes_of_largest_circuits(
+        Playground::from_str(input).expect("Should parse"),
+        circuit_query,
+        metric,
+    )
+    .expect("Should not overflow")
 }
 
 #[cfg(feature = "part2")]
-fn part2(input: &str) -> u64 {
+fn part2(input: &str, reducer: Reducer, metric: Metric) -> i128 {
     // Took 26 minutes 25,34 seconds
-    product_of_last_pair_to_connect_to_single_circuit(
+    reduce_last_pair_to_connect_to_single_circuit(
         Playground::from_str(input).expect("Should parse"),
+        reducer,
+        metric,
     )
+    .expect("Should not overflow")
+}
+
+#[cfg(feature = "part1")]
+fn circuit_query() -> CircuitQuery {
+    CircuitQuery {
+        connect_count: connect_count(),
+        top_count: aoc_2025::option("top")
+            .map(|top_count| top_count.parse().expect("Should parse top count"))
+            .unwrap_or(3),
+        reducer: reducer(),
+    }
+}
+
+fn connect_count() -> usize {
+    aoc_2025::option("connections")
+        .map(|connect_count| {
+            connect_count
+                .parse()
+                .expect("Should parse connection count")
+        })
+        .unwrap_or(1000)
+}
+
+fn reducer() -> Reducer {
+    aoc_2025::option("reducer")
+        .map(|reducer| reducer.parse().expect("Should parse reducer"))
+        .unwrap_or_default()
+}
+
+fn metric() -> Metric {
+    aoc_2025::option("metric")
+        .map(|metric| metric.parse().expect("Should parse metric"))
+        .unwrap_or_default()
+}
+
+/// Connects the `connect_count` closest pairs and reduces the sizes of the `top_count` largest
+/// circuits.
+#[cfg(feature = "part1")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct CircuitQuery {
+    connect_count: usize,
+    top_count: usize,
+    reducer: Reducer,
 }
 
 #[cfg(feature = "part1")]
-fn product_of_size_of_largets_circuits(playground: Playground, connect_count: usize) -> u64 {
+fn reduce_sizes_of_largest_circuits(
+    playground: Playground,
+    query: CircuitQuery,
+    metric: Metric,
+) -> Option<u64> {
     let mut connected_circuits = playground
-        .connect_closest(ConnectCondition::LessThanNPairsConnected(connect_count))
-        .0;
+        .connect_closest(query.connect_count, metric)
+        .into_vec();
     connected_circuits.sort_by_key(|circuit| circuit.0.len());
-    connected_circuits
-        .into_iter()
-        .rev()
-        .take(3)
-        .map(|circuit| circuit.0.len() as u64)
-        .product()
+    query.reducer.reduce(
+        connected_circuits
+            .into_iter()
+            .rev()
+            .take(query.top_count)
+            .map(|circuit| circuit.0.len() as u64),
+    )
 }
 
 #[cfg(feature = "part2")]
-fn product_of_last_pair_to_connect_to_single_circuit(playground: Playground) -> u64 {
-    let (_, last_connected) = playground.connect_closest(ConnectCondition::NotYetSingleCircuit);
-    (last_connected.0.0.x * last_connected.0.1.x) as u64
+fn reduce_last_pair_to_connect_to_single_circuit(
+    playground: Playground,
+    reducer: Reducer,
+    metric: Metric,
+) -> Option<i128> {
+    let last_connected = playground
+        .connect_to_single_circuit(metric)
+        .expect("Should have at least two junction boxes");
+    reducer.reduce([
+        i128::from(last_connected.0.0.x),
+        i128::from(last_connected.0.1.x),
+    ])
+}
+
+/// Returns how many circuits there are of every size after connecting the `connect_count`
+/// closest pairs.
+#[cfg(feature = "part1")]
+fn size_distribution(
+    playground: &Playground,
+    connect_count: usize,
+    metric: Metric,
+) -> BTreeMap<usize, usize> {
+    playground
+        .connect_closest(connect_count, metric)
+        .iter()
+        .map(|circuit| circuit.0.len())
+        .counts()
+        .into_iter()
+        .collect()
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+enum Reducer {
+    #[default]
+    Product,
+    Sum,
+    Max,
+}
+
+impl Reducer {
+    /// Returns `None` on overflow, an empty product is `1` and an empty maximum is `0`.
+    fn reduce<T>(self, values: impl IntoIterator<Item = T>) -> Option<T>
+    where
+        T: Ord + Zero + One + CheckedAdd + CheckedMul,
+    {
+        let mut values = values.into_iter();
+        match self {
+            Reducer::Product => {
+                values.try_fold(T::one(), |product, value| product.checked_mul(&value))
+            }
+            Reducer::Sum => values.try_fold(T::zero(), |sum, value| sum.checked_add(&value)),
+            Reducer::Max => Some(values.max().unwrap_or_else(T::zero)),
+        }
+    }
+}
+
+impl FromStr for Reducer {
+    type Err = ParseReducerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Self::Product),
+            "sum" => Ok(Self::Sum),
+            "max" => Ok(Self::Max),
+            _ => Err(ParseReducerError::Unknown {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum ParseReducerError {
+    #[error("Unknown reducer '{value}'")]
+    Unknown { value: String },
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 struct Playground(Box<[JunctionBox]>);
 
 impl Playground {
-    fn connect_closest(&self, until: ConnectCondition) -> (Box<[Circuit]>, LastJunctionBoxPair) {
-        #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-        struct JunctionBoxPair {
-            first_index: usize,
-            second_index: usize,
+    #[cfg(feature = "part1")]
+    fn connect_closest(&self, connect_count: usize, metric: Metric) -> Box<[Circuit]> {
+        let mut circuits = DisjointSet::new(self.0.len());
+        for pair in self.closest_pairs(connect_count, metric) {
+            circuits.union(pair.first_index, pair.second_index);
         }
+        circuits
+            .sets()
+            .into_iter()
+            .map(|circuit| Circuit(circuit.into_iter().map(|index| self.0[index]).collect()))
+            .collect()
+    }
 
-        #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-        struct JunctionBoxPairDistance(u64);
+    /// The last pair connected when connecting the closest pairs one after another is the longest
+    /// edge of the minimum spanning tree, as every shorter edge is already part of a circuit.
+    #[cfg(feature = "part2")]
+    fn connect_to_single_circuit(&self, metric: Metric) -> Option<LastJunctionBoxPair> {
+        let last_pair = self.minimum_spanning_tree(metric).into_iter().max()?;
+        Some(LastJunctionBoxPair((
+            self.0[last_pair.first_index],
+            self.0[last_pair.second_index],
+        )))
+    }
 
-        let mut pair_distances: HashMap<JunctionBoxPair, JunctionBoxPairDistance> = HashMap::new();
-        for first_index in 0..self.0.len() {
-            for second_index in 0..self.0.len() {
-                if first_index == second_index {
-                    continue;
+    /// Returns the `count` closest pairs ordered by distance, keeping only them in a bounded heap
+    /// instead of materialising every pair.
+    fn closest_pairs(&self, count: usize, metric: Metric) -> Vec<JunctionBoxPair> {
+        let mut closest_pairs = BinaryHeap::with_capacity(count + 1);
+        for second_index in 1..self.0.len() {
+            for first_index in 0..second_index {
+                let pair = self.pair(first_index, second_index, metric);
+                if closest_pairs.len() < count {
+                    closest_pairs.push(pair);
+                } else if closest_pairs
+                    .peek()
+                    .is_some_and(|farthest| pair < *farthest)
+                {
+                    closest_pairs.pop();
+                    closest_pairs.push(pair);
                 }
-                let key = JunctionBoxPair {
-                    first_index: first_index.min(second_index),
-                    second_index: first_index.max(second_index),
-                };
-                pair_distances.entry(key).or_insert_with(|| {
-                    JunctionBoxPairDistance(self.0[first_index].distance_to(&self.0[second_index]))
-                });
             }
         }
-        let mut pair_distances = pair_distances.into_iter().collect::<Vec<_>>();
-        pair_distances.sort_by_key(|a| a.1);
-
-        let mut circuits = (0..self.0.len())
-            .map(|index| vec![index])
-            .collect::<Vec<_>>();
+        closest_pairs.into_sorted_vec()
+    }
 
-        let mut pair_index = 0;
-        let last_pair = loop {
-            let current_pair = pair_distances[pair_index].0;
-
-            let first_circuit_index = circuits
-                .iter()
-                .position(|circuit| circuit.contains(&current_pair.first_index))
-                .expect("Should find junction box in indices");
-            let second_circuit_index = circuits
-                .iter()
-                .position(|circuit| circuit.contains(&current_pair.second_index))
-                .expect("Should find junction box in indices");
-            if first_circuit_index != second_circuit_index {
-                let new_circuit = circuits[first_circuit_index]
-                    .iter()
-                    .copied()
-                    .chain(circuits[second_circuit_index].iter().copied())
-                    .unique()
-                    .collect::<Vec<_>>();
-                circuits.retain(|circuit| {
-                    !circuit.contains(&current_pair.first_index)
-                        && !circuit.contains(&current_pair.second_index)
-                });
-                circuits.push(new_circuit);
+    /// Prim's algorithm on the complete graph in `O(n²)` time and `O(n)` memory.
+    fn minimum_spanning_tree(&self, metric: Metric) -> Vec<JunctionBoxPair> {
+        let mut edges = Vec::with_capacity(self.0.len().saturating_sub(1));
+        let mut closest_in_tree: Vec<Option<JunctionBoxPair>> = vec![None; self.0.len()];
+        let mut in_tree = vec![false; self.0.len()];
+        let mut next_index = Some(0).filter(|_| !self.0.is_empty());
+        while let Some(index) = next_index.take() {
+            in_tree[index] = true;
+            if let Some(edge) = closest_in_tree[index] {
+                edges.push(edge);
             }
-
-            match until {
-                #[cfg(feature = "part1")]
-                ConnectCondition::LessThanNPairsConnected(n) => {
-                    if (pair_index + 1) >= n {
-                        break current_pair;
-                    }
+            let mut next_closest: Option<JunctionBoxPair> = None;
+            for other_index in 0..self.0.len() {
+                if in_tree[other_index] {
+                    continue;
                 }
-                #[cfg(feature = "part2")]
-                ConnectCondition::NotYetSingleCircuit => {
-                    if circuits.len() <= 1 {
-                        break current_pair;
-                    }
+                let pair = self.pair(index, other_index, metric);
+                let closest =
+                    closest_in_tree[other_index].map_or(pair, |closest| closest.min(pair));
+                closest_in_tree[other_index] = Some(closest);
+                if next_closest.is_none_or(|next_closest| closest < next_closest) {
+                    next_closest = Some(closest);
+                    next_index = Some(other_index);
                 }
             }
+        }
+        edges
+    }
 
-            pair_index += 1;
-        };
-
-        (
-            circuits
-                .into_iter()
-                .map(|circuit| Circuit(circuit.into_iter().map(|index| self.0[index]).collect()))
-                .collect(),
-            LastJunctionBoxPair((
-                self.0[last_pair.first_index],
-                self.0[last_pair.second_index],
-            )),
-        )
+    fn pair(&self, first_index: usize, second_index: usize, metric: Metric) -> JunctionBoxPair {
+        let (first_index, second_index) =
+            if (self.0[first_index], first_index) <= (self.0[second_index], second_index) {
+                (first_index, second_index)
+            } else {
+                (second_index, first_index)
+            };
+        JunctionBoxPair {
+            distance: self.0[first_index].distance_to(&self.0[second_index], metric),
+            first: self.0[first_index],
+            second: self.0[second_index],
+            first_index,
+            second_index,
+        }
     }
 }
 
+/// Ordered by distance, ties are broken by the coordinates of the junction boxes, so the order
+/// does not depend on the order of the input, and only for equal junction boxes by the indices.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+struct JunctionBoxPair {
+    distance: u128,
+    first: JunctionBox,
+    second: JunctionBox,
+    first_index: usize,
+    second_index: usize,
+}
+
 impl FromStr for Playground {
     type Err = ParsePlaygroundError;
 
@@ -155,7 +339,7 @@ enum ParsePlaygroundError {
     ParseJunctionBox(#[from] ParseJunctionBoxError),
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 struct JunctionBox {
     x: i64,
     y: i64,
@@ -163,9 +347,23 @@ struct JunctionBox {
 }
 
 impl JunctionBox {
-    fn distance_to(&self, other: &JunctionBox) -> u64 {
-        ((self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)).isqrt()
-            as u64
+    /// Coordinates are limited to this magnitude, so the squared euclidean distance fits exactly
+    /// into a `u128`.
+    const MAXIMUM_COORDINATE: i64 = 1 << 62;
+
+    /// Returns a distance which orders like the metric, for the euclidean metric it is squared.
+    fn distance_to(&self, other: &JunctionBox, metric: Metric) -> u128 {
+        let differences = [
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        ]
+        .map(u128::from);
+        match metric {
+            Metric::Euclidean => differences.iter().map(|difference| difference.pow(2)).sum(),
+            Metric::Manhattan => differences.iter().sum(),
+            Metric::Chebyshev => differences.into_iter().max().unwrap_or_default(),
+        }
     }
 }
 
@@ -178,10 +376,18 @@ impl FromStr for JunctionBox {
             .collect::<Vec<_>>()
             .try_into()
             .map_err(|_| ParseJunctionBoxError::CoordinateCountMismatch)?;
+        let [x, y, z] = [x, y, z].map(|coordinate| {
+            let coordinate = coordinate.parse::<i64>()?;
+            if coordinate.unsigned_abs() > Self::MAXIMUM_COORDINATE.unsigned_abs() {
+                Err(ParseJunctionBoxError::CoordinateOutOfRange { coordinate })
+            } else {
+                Ok(coordinate)
+            }
+        });
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
-            z: z.parse()?,
+            x: x?,
+            y: y?,
+            z: z?,
         })
     }
 }
@@ -192,24 +398,185 @@ enum ParseJunctionBoxError {
     CoordinateCountMismatch,
     #[error("Failed to parse coordinate: {0}")]
     ParseCoordinate(#[from] ParseIntError),
+    #[error("Coordinate {coordinate} is out of range")]
+    CoordinateOutOfRange { coordinate: i64 },
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-enum ConnectCondition {
-    #[cfg(feature = "part1")]
-    LessThanNPairsConnected(usize),
-    #[cfg(feature = "part2")]
-    NotYetSingleCircuit,
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+enum Metric {
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
 }
 
+impl Metric {
+    /// Returns the distance for one compared by [`JunctionBox::distance_to`].
+    fn actual_distance(self, distance: u128) -> f64 {
+        match self {
+            Metric::Euclidean => (distance as f64).sqrt(),
+            Metric::Manhattan | Metric::Chebyshev => distance as f64,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = ParseMetricError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "euclidean" => Ok(Self::Euclidean),
+            "manhattan" => Ok(Self::Manhattan),
+            "chebyshev" => Ok(Self::Chebyshev),
+            _ => Err(ParseMetricError::Unknown {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum ParseMetricError {
+    #[error("Unknown metric '{value}'")]
+    Unknown { value: String },
+}
+
+#[cfg(feature = "part1")]
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 struct Circuit(Box<[JunctionBox]>);
 
+#[cfg(feature = "part2")]
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct LastJunctionBoxPair((JunctionBox, JunctionBox));
 
+mod circuit_graph {
+    use std::io::Write;
+    use std::str::FromStr;
+
+    use aoc_2025::disjoint_set::DisjointSet;
+    use itertools::Itertools;
+
+    use super::{JunctionBoxPair, Metric, Playground};
+
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub enum GraphFormat {
+        /// Graphviz with one cluster per circuit and the distances as edge labels.
+        Dot,
+        /// An object with the junction boxes, the circuits as their indices and the edge list.
+        Json,
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+    pub enum GraphEdges {
+        /// The pairs connected for part 1.
+        #[default]
+        Closest,
+        /// The minimum spanning tree connecting everything for part 2.
+        SpanningTree,
+    }
+
+    impl FromStr for GraphEdges {
+        type Err = ParseGraphEdgesError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "closest" => Ok(Self::Closest),
+                "spanning-tree" => Ok(Self::SpanningTree),
+                _ => Err(ParseGraphEdgesError::Unknown {
+                    value: s.to_string(),
+                }),
+            }
+        }
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum ParseGraphEdgesError {
+        #[error("Unknown graph edges '{value}'")]
+        Unknown { value: String },
+    }
+
+    /// Writes the circuits formed by connecting `edges` together with the edges themselves.
+    pub fn export(
+        playground: &Playground,
+        edges: &[JunctionBoxPair],
+        metric: Metric,
+        format: GraphFormat,
+        mut writer: impl Write,
+    ) -> std::io::Result<()> {
+        let mut circuits = DisjointSet::new(playground.0.len());
+        for edge in edges {
+            circuits.union(edge.first_index, edge.second_index);
+        }
+        let circuits = circuits.sets();
+        match format {
+            GraphFormat::Dot => {
+                writeln!(writer, "graph circuits {{")?;
+                for (circuit_index, circuit) in circuits.iter().enumerate() {
+                    writeln!(writer, "  subgraph cluster_{circuit_index} {{")?;
+                    for &index in circuit {
+                        let junction_box = playground.0[index];
+                        writeln!(
+                            writer,
+                            "    n{index} [label=\"{},{},{}\"];",
+                            junction_box.x, junction_box.y, junction_box.z
+                        )?;
+                    }
+                    writeln!(writer, "  }}")?;
+                }
+                for edge in edges {
+                    writeln!(
+                        writer,
+                        "  n{} -- n{} [label=\"{}\"];",
+                        edge.first_index,
+                        edge.second_index,
+                        metric.actual_distance(edge.distance)
+                    )?;
+                }
+                writeln!(writer, "}}")?;
+            }
+            GraphFormat::Json => {
+                writeln!(
+                    writer,
+                    "{{\n  \"junction_boxes\": [{}],",
+                    playground
+                        .0
+                        .iter()
+                        .map(|junction_box| format!(
+                            "{{\"x\":{},\"y\":{},\"z\":{}}}",
+                            junction_box.x, junction_box.y, junction_box.z
+                        ))
+                        .join(",")
+                )?;
+                writeln!(
+                    writer,
+                    "  \"circuits\": [{}],",
+                    circuits
+                        .iter()
+                        .map(|circuit| format!("[{}]", circuit.iter().join(",")))
+                        .join(",")
+                )?;
+                write!(writer, "  \"edges\": [")?;
+                for (position, edge) in edges.iter().enumerate() {
+                    write!(
+                        writer,
+                        "{}\n    {{\"from\":{},\"to\":{},\"distance\":{}}}",
+                        if position == 0 { "" } else { "," },
+                        edge.first_index,
+                        edge.second_index,
+                        metric.actual_distance(edge.distance)
+                    )?;
+                }
+                writeln!(writer, "\n  ]\n}}")?;
+            }
+        }
+        writer.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
 
     #[test]
@@ -237,13 +604,18 @@ mod tests {
 425,690,689";
 
         // Act
-        let part1 = product_of_size_of_largets_circuits(
+        let part1 = reduce_sizes_of_largest_circuits(
             Playground::from_str(input).expect("Should parse"),
-            10,
+            CircuitQuery {
+                connect_count: 10,
+                top_count: 3,
+                reducer: Reducer::Product,
+            },
+            Metric::Euclidean,
         );
 
         // Assert
-        assert_eq!(part1, 40);
+        assert_eq!(part1, Some(40));
     }
 
     #[test]
@@ -271,11 +643,240 @@ mod tests {
 425,690,689";
 
         // Act
-        let part2 = product_of_last_pair_to_connect_to_single_circuit(
+        let part2 = reduce_last_pair_to_connect_to_single_circuit(
             Playground::from_str(input).expect("Should parse"),
+            Reducer::Product,
+            Metric::Euclidean,
+        );
+
+        // Assert
+        assert_eq!(part2, Some(25272));
+    }
+
+    #[test]
+    fn test_closest_pairs_and_minimum_spanning_tree() {
+        // Arrange
+        let playground = Playground(
+            (0..40)
+                .map(|index| JunctionBox {
+                    x: index * 37 % 23,
+                    y: index * 11 % 17,
+                    z: index * 7 % 13,
+                })
+                .collect(),
+        );
+        let mut all_pairs = (0..40)
+            .flat_map(|second| (0..second).map(move |first| (first, second)))
+            .map(|(first, second)| playground.pair(first, second, Metric::Euclidean))
+            .collect::<Vec<_>>();
+        all_pairs.sort();
+
+        // Act
+        let closest_pairs = playground.closest_pairs(25, Metric::Euclidean);
+        let minimum_spanning_tree = playground.minimum_spanning_tree(Metric::Euclidean);
+
+        // Assert
+        assert_eq!(closest_pairs, all_pairs[..25]);
+        let mut circuits = DisjointSet::new(40);
+        let kruskal = all_pairs
+            .into_iter()
+            .filter(|pair| circuits.union(pair.first_index, pair.second_index))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            minimum_spanning_tree.into_iter().collect::<BTreeSet<_>>(),
+            kruskal
+        );
+    }
+
+    #[test]
+    fn test_metrics_and_ties() {
+        // Arrange
+        let playground = Playground::from_str("2,0,0\n0,0,0\n1,1,1").expect("Should parse");
+        let reversed = Playground::from_str("1,1,1\n0,0,0\n2,0,0").expect("Should parse");
+        let closest = |playground: &Playground, metric| {
+            let [pair] = playground.closest_pairs(1, metric)[..] else {
+                panic!("Should have one closest pair");
+            };
+            (pair.first, pair.second, pair.distance)
+        };
+        let junction_box = |x, y, z| JunctionBox { x, y, z };
+
+        // Act
+        let euclidean = closest(&playground, Metric::Euclidean);
+        let manhattan = closest(&playground, Metric::Manhattan);
+        let chebyshev = closest(&playground, Metric::Chebyshev);
+        let reversed_chebyshev = closest(&reversed, Metric::Chebyshev);
+
+        // Assert
+        assert_eq!(euclidean, (junction_box(0, 0, 0), junction_box(1, 1, 1), 3));
+        assert_eq!(manhattan, (junction_box(0, 0, 0), junction_box(2, 0, 0), 2));
+        assert_eq!(chebyshev, (junction_box(0, 0, 0), junction_box(1, 1, 1), 1));
+        assert_eq!(reversed_chebyshev, chebyshev);
+    }
+
+    #[test]
+    fn test_coordinate_range() {
+        // Arrange
+        let inputs = [
+            "-4611686018427387904,4611686018427387904,0",
+            "-4611686018427387905,0,0",
+            "0,0,-9223372036854775808",
+        ];
+
+        // Act
+        let [limits, beyond_limit, minimum] = inputs.map(JunctionBox::from_str);
+
+        // Assert
+        assert_eq!(
+            limits.expect("Should parse"),
+            JunctionBox {
+                x: -(1 << 62),
+                y: 1 << 62,
+                z: 0,
+            }
+        );
+        assert!(matches!(
+            beyond_limit,
+            Err(ParseJunctionBoxError::CoordinateOutOfRange {
+                coordinate: -4611686018427387905
+            })
+        ));
+        assert!(matches!(
+            minimum,
+            Err(ParseJunctionBoxError::CoordinateOutOfRange {
+                coordinate: i64::MIN
+            })
+        ));
+    }
+
+    #[test]
+    fn test_export_graph() {
+        // Arrange
+        let playground =
+            Playground::from_str("0,0,0\n3,4,0\n3,4,12\n100,0,0").expect("Should parse");
+        let edges = playground.closest_pairs(2, Metric::Euclidean);
+        let mut dot = Vec::new();
+        let mut json = Vec::new();
+
+        // Act
+        circuit_graph::export(
+            &playground,
+            &edges,
+            Metric::Euclidean,
+            circuit_graph::GraphFormat::Dot,
+            &mut dot,
+        )
+        .expect("Should export dot");
+        circuit_graph::export(
+            &playground,
+            &edges,
+            Metric::Euclidean,
+            circuit_graph::GraphFormat::Json,
+            &mut json,
+        )
+        .expect("Should export json");
+
+        // Assert
+        assert_eq!(
+            String::from_utf8(dot).expect("Should be utf-8"),
+            r#"graph circuits {
+  subgraph cluster_0 {
+    n0 [label="0,0,0"];
+    n1 [label="3,4,0"];
+    n2 [label="3,4,12"];
+  }
+  subgraph cluster_1 {
+    n3 [label="100,0,0"];
+  }
+  n0 -- n1 [label="5"];
+  n1 -- n2 [label="12"];
+}
+"#
+        );
+        assert_eq!(
+            String::from_utf8(json).expect("Should be utf-8"),
+            r#"{
+  "junction_boxes": [{"x":0,"y":0,"z":0},{"x":3,"y":4,"z":0},{"x":3,"y":4,"z":12},{"x":100,"y":0,"z":0}],
+  "circuits": [[0,1,2],[3]],
+  "edges": [
+    {"from":0,"to":1,"distance":5},
+    {"from":1,"to":2,"distance":12}
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_size_distribution_and_reducers() {
+        // Arrange
+        let playground = Playground::from_str(
+            "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689",
+        )
+        .expect("Should parse");
+        let query = |reducer| CircuitQuery {
+            connect_count: 10,
+            top_count: 3,
+            reducer,
+        };
+
+        // Act
+        let distribution = size_distribution(&playground, 10, Metric::Euclidean);
+        let sum = reduce_sizes_of_largest_circuits(
+            playground.clone(),
+            query(Reducer::Sum),
+            Metric::Euclidean,
+        );
+        let max =
+            reduce_sizes_of_largest_circuits(playground, query(Reducer::Max), Metric::Euclidean);
+
+        // Assert
+        assert_eq!(
+            distribution,
+            BTreeMap::from([(1, 7), (2, 2), (4, 1), (5, 1)])
+        );
+        assert_eq!(sum, Some(11));
+        assert_eq!(max, Some(5));
+    }
+
+    #[test]
+    fn test_last_pair_with_negative_x() {
+        // Arrange
+        let playground = Playground::from_str("-3,0,0\n-5,4,0\n-90,0,0").expect("Should parse");
+
+        // Act
+        let product = reduce_last_pair_to_connect_to_single_circuit(
+            playground.clone(),
+            Reducer::Product,
+            Metric::Euclidean,
+        );
+        let sum = reduce_last_pair_to_connect_to_single_circuit(
+            playground,
+            Reducer::Sum,
+            Metric::Euclidean,
         );
 
         // Assert
-        assert_eq!(part2, 25272);
+        assert_eq!(product, Some(450));
+        assert_eq!(sum, Some(-95));
     }
 }
@@ -1,10 +1,35 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+#[cfg(feature = "part2")]
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::BufWriter;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[cfg(feature = "part2")]
 use itertools::Itertools;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 fn main() {
+    if let Some(path) = aoc_2025::option("svg") {
+        let list = RedTilesList::from_str(INPUT).expect("Should parse");
+        let file = File::create(path).expect("Should create svg");
+        visualisation::write_svg(&list, BufWriter::new(file)).expect("Should write svg");
+    }
+    if let Some(count) = aoc_2025::option("top") {
+        let count = count.parse().expect("Should parse rectangle count");
+        let list = RedTilesList::from_str(INPUT).expect("Should parse");
+        #[cfg(feature = "part1")]
+        for (first, second, area) in list.find_biggest_rectangles(count) {
+            println!("Part 1 rectangle from {first} to {second} with area {area}");
+        }
+        #[cfg(feature = "part2")]
+        for (first, second, area) in list.find_biggest_rectangles_inside_loop(count) {
+            println!("Part 2 rectangle from {first} to {second} with area {area}");
+        }
+    }
     aoc_2025::aoc!(INPUT, part1, part2);
 }
 
@@ -14,7 +39,10 @@ const INPUT: &str = include_str!("../input/input.day09");
 fn part1(input: &str) -> u64 {
     // Took 11 minutes 24,12 seconds
     let list = RedTilesList::from_str(input).expect("Should parse");
-    let (_, _, area) = list.find_biggest_rectangle().expect("Should not be empty");
+    let (_, _, area) = *list
+        .find_biggest_rectangles(1)
+        .first()
+        .expect("Should not be empty");
     area
 }
 
@@ -22,78 +50,383 @@ fn part1(input: &str) -> u64 {
 fn part2(input: &str) -> u64 {
     // Took 2 hours 28 minutes 19,48 seconds
     let list = RedTilesList::from_str(input).expect("Should parse");
-    let (_, _, area) = list
-        .find_biggest_rectangle_in_bounding_box()
+    list.validate().expect("Should be a valid loop");
+    let (_, _, area) = *list
+        .find_biggest_rectangles_inside_loop(1)
+        .first()
         .expect("Should not be empty");
     area
 }
 
+/// Two opposite corners and the area of a rectangle.
+type Rectangle<'a> = (&'a RedTileLocation, &'a RedTileLocation, u64);
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 struct RedTilesList(Box<[RedTileLocation]>);
 
 impl RedTilesList {
     #[cfg(feature = "part1")]
-    fn find_biggest_rectangle(&self) -> Option<(&RedTileLocation, &RedTileLocation, u64)> {
-        (0..self.0.len())
-            .flat_map(|first_index| {
-                (0..self.0.len()).map(move |second_index| {
-                    (
-                        &self.0[first_index],
-                        &self.0[second_index],
-                        self.0[first_index].area_with(&self.0[second_index]),
-                    )
-                })
-            })
-            .max_by_key(|(_, _, area)| *area)
+    fn find_biggest_rectangles(&self, count: usize) -> Vec<Rectangle<'_>> {
+        self.find_biggest_rectangles_where(count, |_, _| true)
     }
 
+    /// Tests the pairs against a [`TileMask`] of the loop in `O(1)` each, skipping the pairs
+    /// which cannot beat the `count` biggest rectangles found so far.
     #[cfg(feature = "part2")]
-    fn find_biggest_rectangle_in_bounding_box(
+    fn find_biggest_rectangles_inside_loop(&self, count: usize) -> Vec<Rectangle<'_>> {
+        let mask = TileMask::new(&self.0);
+        let compressed = self
+            .0
+            .iter()
+            .map(|tile| mask.compress(tile))
+            .collect::<Vec<_>>();
+        self.find_biggest_rectangles_where(count, |first_index, second_index| {
+            mask.contains_rectangle(compressed[first_index], compressed[second_index])
+        })
+    }
+
+    /// Searches the pairs of red tiles from the tiles with the biggest bound of their area
+    /// downwards, which is the area spanned towards the farthest corner of the bounding box of all
+    /// tiles, in parallel chunks. Stops before the first chunk whose bound cannot beat the `count`
+    /// biggest rectangles accepted by `accepts` so far, and returns them ordered by descending area
+    /// and ascending indices.
+    fn find_biggest_rectangles_where(
         &self,
-    ) -> Option<(&RedTileLocation, &RedTileLocation, u64)> {
-        fn line_in_rectangle(
-            line: (&RedTileLocation, &RedTileLocation),
-            rectangle: (&RedTileLocation, &RedTileLocation),
-        ) -> bool {
-            line.0.x.min(line.1.x) < rectangle.0.x.max(rectangle.1.x)
-                && line.0.x.max(line.1.x) > rectangle.0.x.min(rectangle.1.x)
-                && line.0.y.min(line.1.y) < rectangle.0.y.max(rectangle.1.y)
-                && line.0.y.max(rectangle.1.y) > rectangle.0.y.min(rectangle.1.y)
+        count: usize,
+        accepts: impl Fn(usize, usize) -> bool + Sync,
+    ) -> Vec<Rectangle<'_>> {
+        const CHUNK_SIZE: usize = 64;
+
+        let tiles = &self.0;
+        let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (
+            tiles.iter().map(|tile| tile.x).min(),
+            tiles.iter().map(|tile| tile.x).max(),
+            tiles.iter().map(|tile| tile.y).min(),
+            tiles.iter().map(|tile| tile.y).max(),
+        ) else {
+            return Vec::new();
+        };
+        let mut order = tiles
+            .iter()
+            .enumerate()
+            .map(|(index, tile)| {
+                let width = tile.x.abs_diff(min_x).max(tile.x.abs_diff(max_x)) + 1;
+                let height = tile.y.abs_diff(min_y).max(tile.y.abs_diff(max_y)) + 1;
+                (width * height, index)
+            })
+            .collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&(area_bound, index)| (Reverse(area_bound), index));
+        let mut positions = vec![0; tiles.len()];
+        for (position, &(_, index)) in order.iter().enumerate() {
+            positions[index] = position;
         }
 
-        (0..self.0.len())
-            .cartesian_product(0..self.0.len())
-            .filter(|(first_index, second_index)| {
-                let first = &self.0[*first_index];
-                let second = &self.0[*second_index];
-
-                for index in 0..self.0.len() {
-                    let point_a = if index == 0 {
-                        &self.0[self.0.len() - 1]
-                    } else {
-                        &self.0[index - 1]
-                    };
-                    let point_b = &self.0[index];
-                    let line = (point_a, point_b);
-
-                    if line_in_rectangle(line, (first, second)) {
-                        return false;
+        let is_beaten = |heap: &BinaryHeap<_>, pair| {
+            heap.len() == count && heap.peek().is_none_or(|worst| *worst <= pair)
+        };
+        let push_bounded = |heap: &mut BinaryHeap<_>, pair| {
+            heap.push(pair);
+            if heap.len() > count {
+                heap.pop();
+            }
+        };
+        let mut biggest = BinaryHeap::new();
+        for (chunk_index, chunk) in order.chunks(CHUNK_SIZE).enumerate() {
+            let (area_bound, _) = chunk[0];
+            if is_beaten(&biggest, (Reverse(area_bound), 0, 0)) {
+                break;
+            }
+            let chunk_biggest = chunk
+                .par_iter()
+                .enumerate()
+                .fold(BinaryHeap::new, |mut heap, (offset, &(_, index))| {
+                    let position = chunk_index * CHUNK_SIZE + offset;
+                    for (other_index, &other_position) in positions.iter().enumerate() {
+                        // Every pair is searched from the tile which comes first in the order.
+                        if other_position <= position {
+                            continue;
+                        }
+                        let (first_index, second_index) =
+                            (index.min(other_index), index.max(other_index));
+                        let area = tiles[first_index].area_with(&tiles[second_index]);
+                        let pair = (Reverse(area), first_index, second_index);
+                        if is_beaten(&biggest, pair) || is_beaten(&heap, pair) {
+                            continue;
+                        }
+                        if accepts(first_index, second_index) {
+                            push_bounded(&mut heap, pair);
+                        }
                     }
-                }
-
-                true
+                    heap
+                })
+                .reduce(BinaryHeap::new, |mut heap, other| {
+                    for pair in other {
+                        push_bounded(&mut heap, pair);
+                    }
+                    heap
+                });
+            for pair in chunk_biggest {
+                push_bounded(&mut biggest, pair);
+            }
+        }
+        biggest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(Reverse(area), first_index, second_index)| {
+                self.rectangle((area, first_index, second_index))
             })
-            .map(|(first_index, second_index)| {
-                (
-                    &self.0[first_index],
-                    &self.0[second_index],
-                    self.0[first_index].area_with(&self.0[second_index]),
-                )
+            .collect()
+    }
+
+    fn rectangle(&self, (area, first_index, second_index): (u64, usize, usize)) -> Rectangle<'_> {
+        (&self.0[first_index], &self.0[second_index], area)
+    }
+}
+
+#[cfg(feature = "part2")]
+impl RedTilesList {
+    /// Checks that the red tiles form a closed axis-aligned simple polygon in `O(n²)` and returns
+    /// its shape.
+    fn validate(&self) -> Result<LoopShape, ValidateLoopError> {
+        let tiles = &self.0;
+        if tiles.len() < 4 {
+            return Err(ValidateLoopError::TooFewTiles { count: tiles.len() });
+        }
+        let edges = tiles
+            .iter()
+            .copied()
+            .circular_tuple_windows()
+            .collect::<Vec<(RedTileLocation, RedTileLocation)>>();
+        for (index, (from, to)) in edges.iter().copied().enumerate() {
+            if from == to {
+                return Err(ValidateLoopError::ZeroLengthEdge { index });
+            }
+            if from.x != to.x && from.y != to.y {
+                return Err(ValidateLoopError::NotAxisAligned { index, from, to });
+            }
+        }
+        let mut first_indices = HashMap::new();
+        for (second_index, tile) in tiles.iter().enumerate() {
+            if let Some(first_index) = first_indices.insert(tile, second_index) {
+                return Err(ValidateLoopError::DuplicateTile {
+                    tile: *tile,
+                    first_index,
+                    second_index,
+                });
+            }
+        }
+        for (first_index, second_index) in (0..edges.len()).tuple_combinations() {
+            let (first, second) = (edges[first_index], edges[second_index]);
+            let intersect = if second_index == first_index + 1 {
+                direction(first) == direction((second.1, second.0))
+            } else if first_index == 0 && second_index == edges.len() - 1 {
+                direction(second) == direction((first.1, first.0))
+            } else {
+                let overlap = |first: (u64, u64), second: (u64, u64)| {
+                    first.0.min(first.1) <= second.0.max(second.1)
+                        && second.0.min(second.1) <= first.0.max(first.1)
+                };
+                overlap((first.0.x, first.1.x), (second.0.x, second.1.x))
+                    && overlap((first.0.y, first.1.y), (second.0.y, second.1.y))
+            };
+            if intersect {
+                return Err(ValidateLoopError::SelfIntersection {
+                    first_index,
+                    second_index,
+                });
+            }
+        }
+
+        let doubled_signed_area = edges
+            .iter()
+            .map(|(from, to)| {
+                i128::from(from.x) * i128::from(to.y) - i128::from(to.x) * i128::from(from.y)
             })
-            .max_by_key(|(_, _, area)| *area)
+            .sum::<i128>();
+        let area = doubled_signed_area.unsigned_abs() / 2;
+        let perimeter = edges
+            .iter()
+            .map(|(from, to)| u128::from(from.x.abs_diff(to.x) + from.y.abs_diff(to.y)))
+            .sum::<u128>();
+        Ok(LoopShape {
+            orientation: if doubled_signed_area > 0 {
+                Orientation::Clockwise
+            } else {
+                Orientation::CounterClockwise
+            },
+            area,
+            tile_count: area + perimeter / 2 + 1,
+        })
+    }
+}
+
+/// Returns the signs of the steps along an axis-aligned edge.
+#[cfg(feature = "part2")]
+fn direction(
+    (from, to): (RedTileLocation, RedTileLocation),
+) -> (std::cmp::Ordering, std::cmp::Ordering) {
+    (to.x.cmp(&from.x), to.y.cmp(&from.y))
+}
+
+#[cfg(feature = "part2")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct LoopShape {
+    orientation: Orientation,
+    /// The area enclosed by the loop through the centers of the red tiles, by the shoelace formula.
+    area: u128,
+    /// The count of red and green tiles, by Pick's theorem.
+    tile_count: u128,
+}
+
+/// The orientation as seen with the y axis pointing downwards.
+#[cfg(feature = "part2")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[cfg(feature = "part2")]
+#[derive(thiserror::Error, Debug)]
+enum ValidateLoopError {
+    #[error("Loop needs at least 4 red tiles, but has {count}")]
+    TooFewTiles { count: usize },
+    #[error("Edge {index} has zero length")]
+    ZeroLengthEdge { index: usize },
+    #[error("Edge {index} from {from} to {to} is not axis-aligned")]
+    NotAxisAligned {
+        index: usize,
+        from: RedTileLocation,
+        to: RedTileLocation,
+    },
+    #[error("Red tile {tile} appears at {first_index} and {second_index}")]
+    DuplicateTile {
+        tile: RedTileLocation,
+        first_index: usize,
+        second_index: usize,
+    },
+    #[error("Edges {first_index} and {second_index} intersect")]
+    SelfIntersection {
+        first_index: usize,
+        second_index: usize,
+    },
+}
+
+/// The red and green tiles of a loop on a coordinate-compressed grid. Every coordinate of a red
+/// tile gets its own column or row, the tiles between two consecutive coordinates share one, and
+/// a ring of padding around everything keeps the outside connected.
+#[cfg(feature = "part2")]
+struct TileMask {
+    xs: Vec<u64>,
+    ys: Vec<u64>,
+    /// Counts of compressed cells outside the loop which contain tiles, summed up from the top
+    /// left with an additional leading row and column of zeros.
+    outside_prefix_sums: Vec<u32>,
+}
+
+#[cfg(feature = "part2")]
+impl TileMask {
+    fn new(tiles: &[RedTileLocation]) -> Self {
+        let xs = tiles
+            .iter()
+            .map(|tile| tile.x)
+            .sorted_unstable()
+            .dedup()
+            .collect::<Vec<_>>();
+        let ys = tiles
+            .iter()
+            .map(|tile| tile.y)
+            .sorted_unstable()
+            .dedup()
+            .collect::<Vec<_>>();
+        let mut mask = Self {
+            xs,
+            ys,
+            outside_prefix_sums: Vec::new(),
+        };
+        let (columns, rows) = (2 * mask.xs.len() + 1, 2 * mask.ys.len() + 1);
+
+        let mut boundary = vec![false; rows * columns];
+        for (first, second) in tiles.iter().circular_tuple_windows() {
+            let (first_column, first_row) = mask.compress(first);
+            let (second_column, second_row) = mask.compress(second);
+            for row in first_row.min(second_row)..=first_row.max(second_row) {
+                for column in first_column.min(second_column)..=first_column.max(second_column) {
+                    boundary[row * columns + column] = true;
+                }
+            }
+        }
+
+        let mut outside = vec![false; rows * columns];
+        outside[0] = true;
+        let mut stack = vec![(0_usize, 0_usize)];
+        while let Some((row, column)) = stack.pop() {
+            let neighbours = [
+                row.checked_sub(1).map(|row| (row, column)),
+                Some((row + 1, column)).filter(|(row, _)| *row < rows),
+                column.checked_sub(1).map(|column| (row, column)),
+                Some((row, column + 1)).filter(|(_, column)| *column < columns),
+            ];
+            for (row, column) in neighbours.into_iter().flatten() {
+                let index = row * columns + column;
+                if !boundary[index] && !outside[index] {
+                    outside[index] = true;
+                    stack.push((row, column));
+                }
+            }
+        }
+
+        let mut outside_prefix_sums = vec![0; (rows + 1) * (columns + 1)];
+        for row in 0..rows {
+            for column in 0..columns {
+                let is_outside_with_tiles = outside[row * columns + column]
+                    && has_tiles(&mask.ys, row)
+                    && has_tiles(&mask.xs, column);
+                outside_prefix_sums[(row + 1) * (columns + 1) + column + 1] =
+                    u32::from(is_outside_with_tiles)
+                        + outside_prefix_sums[row * (columns + 1) + column + 1]
+                        + outside_prefix_sums[(row + 1) * (columns + 1) + column]
+                        - outside_prefix_sums[row * (columns + 1) + column];
+            }
+        }
+        mask.outside_prefix_sums = outside_prefix_sums;
+        mask
+    }
+
+    /// Returns the compressed column and row of a red tile.
+    fn compress(&self, tile: &RedTileLocation) -> (usize, usize) {
+        let compress = |coordinates: &[u64], coordinate| {
+            2 * coordinates
+                .binary_search(&coordinate)
+                .expect("Should be coordinate of a red tile")
+                + 1
+        };
+        (compress(&self.xs, tile.x), compress(&self.ys, tile.y))
+    }
+
+    /// Returns whether the rectangle spanned by two compressed red tiles has only red and green
+    /// tiles in `O(1)`.
+    fn contains_rectangle(&self, first: (usize, usize), second: (usize, usize)) -> bool {
+        let columns = 2 * self.xs.len() + 2;
+        let (left, right) = (first.0.min(second.0), first.0.max(second.0) + 1);
+        let (top, bottom) = (first.1.min(second.1), first.1.max(second.1) + 1);
+        self.outside_prefix_sums[bottom * columns + right]
+            + self.outside_prefix_sums[top * columns + left]
+            == self.outside_prefix_sums[top * columns + right]
+                + self.outside_prefix_sums[bottom * columns + left]
     }
 }
 
+/// Returns whether the compressed column or row contains tiles, which is not the case for the
+/// padding and for the gap between consecutive coordinates.
+#[cfg(feature = "part2")]
+fn has_tiles(coordinates: &[u64], index: usize) -> bool {
+    index % 2 == 1
+        || (index > 0
+            && index < 2 * coordinates.len()
+            && coordinates[index / 2] - coordinates[index / 2 - 1] > 1)
+}
+
 impl FromStr for RedTilesList {
     type Err = ParseRedTilesListError;
 
@@ -124,6 +457,12 @@ impl RedTileLocation {
     }
 }
 
+impl Display for RedTileLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
 impl FromStr for RedTileLocation {
     type Err = ParseRedTileLocationError;
 
@@ -146,6 +485,93 @@ enum ParseRedTileLocationError {
     ParseCoordinate(#[from] ParseIntError),
 }
 
+mod visualisation {
+    use std::io::Write;
+
+    use itertools::Itertools;
+
+    use super::RedTilesList;
+
+    /// Draws the loop through the centers of the red tiles filled with the green tiles, and the
+    /// biggest rectangle of every enabled part labelled with its corners.
+    pub fn write_svg(list: &RedTilesList, mut writer: impl Write) -> std::io::Result<()> {
+        let mut rectangles = Vec::new();
+        #[cfg(feature = "part1")]
+        rectangles.extend(
+            list.find_biggest_rectangles(1)
+                .into_iter()
+                .map(|rectangle| ("Part 1", "orange", rectangle)),
+        );
+        #[cfg(feature = "part2")]
+        rectangles.extend(
+            list.find_biggest_rectangles_inside_loop(1)
+                .into_iter()
+                .map(|rectangle| ("Part 2", "blue", rectangle)),
+        );
+
+        let (min_x, max_x) = list
+            .0
+            .iter()
+            .map(|tile| i128::from(tile.x))
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        let (min_y, max_y) = list
+            .0
+            .iter()
+            .map(|tile| i128::from(tile.y))
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        let unit = ((max_x - min_x).max(max_y - min_y) / 500).max(1);
+        let padding = 10 * unit;
+        writeln!(
+            writer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+            min_x - padding,
+            min_y - padding,
+            max_x - min_x + 2 * padding,
+            max_y - min_y + 2 * padding
+        )?;
+        writeln!(
+            writer,
+            "  <polygon points=\"{}\" fill=\"green\" stroke=\"red\" stroke-width=\"{unit}\"/>",
+            list.0
+                .iter()
+                .map(|tile| format!("{},{}", tile.x, tile.y))
+                .join(" ")
+        )?;
+        for tile in &list.0 {
+            writeln!(
+                writer,
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{unit}\" fill=\"red\"/>",
+                tile.x, tile.y
+            )?;
+        }
+        for (name, colour, (first, second, area)) in rectangles {
+            writeln!(
+                writer,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{colour}\" stroke-width=\"{unit}\"><title>{name}: {area}</title></rect>",
+                first.x.min(second.x),
+                first.y.min(second.y),
+                first.x.abs_diff(second.x),
+                first.y.abs_diff(second.y)
+            )?;
+            for corner in [first, second] {
+                writeln!(
+                    writer,
+                    "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{colour}\">{corner}</text>",
+                    corner.x,
+                    corner.y,
+                    8 * unit
+                )?;
+            }
+        }
+        writeln!(writer, "</svg>")?;
+        writer.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,4 +613,185 @@ mod tests {
         // Assert
         assert_eq!(part2, 24);
     }
+
+    /// Returns the areas of all rectangles inside the loop from the biggest downwards by checking
+    /// every tile with ray casting.
+    fn areas_inside_loop_by_brute_force(list: &RedTilesList) -> Vec<u64> {
+        let edges = list.0.iter().circular_tuple_windows().collect::<Vec<_>>();
+        let is_red_or_green = |x: u64, y: u64| {
+            let on_edge = edges.iter().any(|(first, second)| {
+                (first.x.min(second.x)..=first.x.max(second.x)).contains(&x)
+                    && (first.y.min(second.y)..=first.y.max(second.y)).contains(&y)
+            });
+            let crossings = edges
+                .iter()
+                .filter(|(first, second)| {
+                    first.x == second.x
+                        && first.x > x
+                        && (first.y.min(second.y)..first.y.max(second.y)).contains(&y)
+                })
+                .count();
+            on_edge || crossings % 2 == 1
+        };
+        list.0
+            .iter()
+            .tuple_combinations()
+            .filter(|(first, second)| {
+                (first.x.min(second.x)..=first.x.max(second.x))
+                    .cartesian_product(first.y.min(second.y)..=first.y.max(second.y))
+                    .all(|(x, y)| is_red_or_green(x, y))
+            })
+            .map(|(first, second)| first.area_with(second))
+            .sorted_unstable_by_key(|area| Reverse(*area))
+            .collect()
+    }
+
+    #[test]
+    fn test_part2_on_concave_loops() {
+        // Arrange
+        let loops = [
+            // Example
+            ("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3", 24),
+            // L
+            ("0,0\n4,0\n4,2\n2,2\n2,4\n0,4", 15),
+            // U with a notch open to the top, which no rectangle may span
+            ("0,0\n2,0\n2,3\n4,3\n4,0\n6,0\n6,5\n0,5", 18),
+            // Edges on adjacent rows without any tile between them
+            ("0,0\n10,0\n10,1\n1,1\n1,2\n10,2\n10,3\n0,3", 44),
+            // Stairs
+            ("0,0\n3,0\n3,2\n6,2\n6,4\n9,4\n9,9\n0,9", 60),
+        ];
+
+        for (input, expected) in loops {
+            let list = RedTilesList::from_str(input).expect("Should parse");
+
+            // Act
+            let biggest = list.find_biggest_rectangles_inside_loop(3);
+
+            // Assert
+            let areas = biggest.iter().map(|(_, _, area)| *area).collect::<Vec<_>>();
+            assert_eq!(areas, areas_inside_loop_by_brute_force(&list)[..3]);
+            assert_eq!(areas[0], expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_validate_loop() {
+        // Arrange
+        let parse = |input| RedTilesList::from_str(input).expect("Should parse");
+        let example = parse("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3");
+        let reversed = RedTilesList(example.0.iter().rev().copied().collect());
+
+        // Act
+        let shape = example.validate();
+        let reversed_shape = reversed.validate();
+        let too_few = parse("0,0\n1,0\n1,1").validate();
+        let zero_length = parse("0,0\n2,0\n2,0\n2,2\n0,2").validate();
+        let diagonal = parse("0,0\n2,0\n2,2\n1,3\n0,2").validate();
+        let duplicate = parse("0,0\n2,0\n2,2\n0,2\n0,0\n0,1").validate();
+        let crossing = parse("0,2\n4,2\n4,6\n2,6\n2,0\n0,0").validate();
+        let folding = parse("0,0\n4,0\n2,0\n2,2\n0,2").validate();
+
+        // Assert
+        assert_eq!(
+            shape.expect("Should be valid"),
+            LoopShape {
+                orientation: Orientation::Clockwise,
+                area: 30,
+                tile_count: 46,
+            }
+        );
+        assert_eq!(
+            reversed_shape.expect("Should be valid").orientation,
+            Orientation::CounterClockwise
+        );
+        assert!(matches!(
+            too_few,
+            Err(ValidateLoopError::TooFewTiles { count: 3 })
+        ));
+        assert!(matches!(
+            zero_length,
+            Err(ValidateLoopError::ZeroLengthEdge { index: 1 })
+        ));
+        assert!(matches!(
+            diagonal,
+            Err(ValidateLoopError::NotAxisAligned { index: 2, .. })
+        ));
+        assert!(matches!(
+            duplicate,
+            Err(ValidateLoopError::DuplicateTile {
+                first_index: 0,
+                second_index: 4,
+                ..
+            })
+        ));
+        assert!(matches!(
+            crossing,
+            Err(ValidateLoopError::SelfIntersection {
+                first_index: 0,
+                second_index: 3,
+            })
+        ));
+        assert!(matches!(
+            folding,
+            Err(ValidateLoopError::SelfIntersection {
+                first_index: 0,
+                second_index: 1,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_write_svg() {
+        // Arrange
+        let list = RedTilesList::from_str("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3")
+            .expect("Should parse");
+        let mut svg = Vec::new();
+
+        // Act
+        visualisation::write_svg(&list, &mut svg).expect("Should write svg");
+
+        // Assert
+        let svg = String::from_utf8(svg).expect("Should be utf-8");
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-8 -9 29 26">
+  <polygon points="7,1 11,1 11,7 9,7 9,5 2,5 2,3 7,3" fill="green" stroke="red" stroke-width="1"/>
+  <circle cx="7" cy="1" r="1" fill="red"/>
+"#
+        ));
+        assert!(svg.contains(
+            r#"  <rect x="2" y="1" width="9" height="4" fill="none" stroke="orange" stroke-width="1"><title>Part 1: 50</title></rect>
+  <text x="11" y="1" font-size="8" fill="orange">11,1</text>
+  <text x="2" y="5" font-size="8" fill="orange">2,5</text>
+"#
+        ));
+        assert!(svg.contains(
+            r#"<rect x="2" y="3" width="7" height="2" fill="none" stroke="blue" stroke-width="1"><title>Part 2: 24</title></rect>"#
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_biggest_rectangles() {
+        // Arrange
+        let list = RedTilesList::from_str("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3")
+            .expect("Should parse");
+
+        // Act
+        let biggest = list.find_biggest_rectangles(4);
+
+        // Assert
+        assert_eq!(
+            biggest
+                .into_iter()
+                .map(|(first, second, area)| (first.to_string(), second.to_string(), area))
+                .collect::<Vec<_>>(),
+            [
+                ("11,1".to_string(), "2,5".to_string(), 50),
+                ("11,7".to_string(), "2,3".to_string(), 50),
+                ("9,7".to_string(), "2,3".to_string(), 40),
+                ("7,1".to_string(), "11,7".to_string(), 35),
+            ]
+        );
+    }
 }
@@ -1,5 +1,8 @@
 use crate::count::{bounded, bounded_inclusive};
-use crate::solver::{EquationsCount, SystemOfLinearEquations, VariablesCount};
+use crate::solver::{
+    Eliminated, EquationIndex, EquationsCount, SystemOfLinearEquations, VariableIndex,
+    VariablesCount,
+};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
@@ -12,7 +15,9 @@ use std::str::FromStr;
 use std::time::{Duration, Instant};
 
 fn main() {
-    aoc_2025::aoc!(INPUT, part1, part2);
+    #[cfg(feature = "part2")]
+    let elimination = elimination();
+    aoc_2025::aoc!(INPUT, part1(), part2(elimination));
 }
 
 const INPUT: &str = include_str!("../input/input.day10");
@@ -26,13 +31,21 @@ fn part1(input: &str) -> u64 {
 }
 
 #[cfg(feature = "part2")]
-fn part2(input: &str) -> u64 {
+fn part2(input: &str, elimination: Elimination) -> u64 {
     // Took 5 hours 24 minutes 51,14 seconds with multiple breaks and multiple days
     determine_fewest_button_presses_to_configure_joltage_levels(
         input.parse().expect("Should parse"),
+        elimination,
     )
 }
 
+#[cfg(feature = "part2")]
+fn elimination() -> Elimination {
+    aoc_2025::option("elimination")
+        .map(|elimination| elimination.parse().expect("Should parse elimination"))
+        .unwrap_or_default()
+}
+
 fn determine_fewest_button_presses_to_configure_indicator_lights(manual: Manual) -> u64 {
     manual
         .0
@@ -179,12 +192,17 @@ mod solver {
 
     use nnn::{NNNewType, nnn};
 
-    pub struct SystemOfLinearEquations<State: sealed::SOLE> {
-        data: Vec<Vec<f64>>,
+    /// The values are `f64` for the floating point [`gaussian_elimination`] or `i128` for the
+    /// exact [`fraction_free_elimination`].
+    ///
+    /// [`gaussian_elimination`]: SystemOfLinearEquations::gaussian_elimination
+    /// [`fraction_free_elimination`]: SystemOfLinearEquations::fraction_free_elimination
+    pub struct SystemOfLinearEquations<State: sealed::SOLE, Value = f64> {
+        data: Vec<Vec<Value>>,
         state: State,
     }
 
-    impl SystemOfLinearEquations<Initial> {
+    impl<Value> SystemOfLinearEquations<Initial, Value> {
         pub fn new_with_fn<F, G>(
             equations_count: EquationsCount,
             variables_count: VariablesCount,
@@ -192,8 +210,8 @@ mod solver {
             results_fn: G,
         ) -> Self
         where
-            F: Fn(EquationIndex, VariableIndex) -> f64,
-            G: Fn(EquationIndex) -> f64,
+            F: Fn(EquationIndex, VariableIndex) -> Value,
+            G: Fn(EquationIndex) -> Value,
         {
             let mut data = Vec::with_capacity(equations_count.into_inner());
             for equation_index in 0..equations_count.into_inner() {
@@ -212,8 +230,10 @@ mod solver {
                 state: Initial,
             }
         }
+    }
 
-        pub fn gaussian_elimination(self) -> SystemOfLinearEquations<Eliminated> {
+    impl SystemOfLinearEquations<Initial, f64> {
+        pub fn gaussian_elimination(self) -> SystemOfLinearEquations<Eliminated, f64> {
             let SystemOfLinearEquations { data, state: _ } = self;
             let mut output = SystemOfLinearEquations {
                 data,
@@ -304,7 +324,95 @@ mod solver {
         }
     }
 
-    impl SystemOfLinearEquations<Eliminated> {
+    impl SystemOfLinearEquations<Initial, i128> {
+        /// Eliminates like [`SystemOfLinearEquations::gaussian_elimination`], but without ever
+        /// dividing by the pivot: rows are cross-multiplied with the pivot row and reduced by the
+        /// greatest common divisor of their values instead, so every value stays an exact integer.
+        /// Returns `None` if a value overflows while eliminating.
+        pub fn fraction_free_elimination(
+            self,
+        ) -> Option<SystemOfLinearEquations<Eliminated, i128>> {
+            let SystemOfLinearEquations { mut data, state: _ } = self;
+            let mut dependent_variables = Vec::new();
+            let mut independent_variables = Vec::new();
+            let variables_count = data
+                .first()
+                .map(|first_row| first_row.len().saturating_sub(1))
+                .unwrap_or(0);
+
+            let mut current_row_index = 0;
+            let mut current_column_index = 0;
+            while current_row_index < data.len() && current_column_index < variables_count {
+                // Find the smallest non-zero value for the current column, to keep values small:
+                let Some(best_row_index) = (current_row_index..data.len())
+                    .filter(|row_index| data[*row_index][current_column_index] != 0)
+                    .min_by_key(|row_index| data[*row_index][current_column_index].unsigned_abs())
+                else {
+                    independent_variables.push(VariableIndex(current_column_index));
+                    current_column_index += 1;
+                    continue;
+                };
+
+                // Swap rows, mark this column as dependent and make the pivot positive:
+                data.swap(current_row_index, best_row_index);
+                dependent_variables.push(VariableIndex(current_column_index));
+                if data[current_row_index][current_column_index] < 0 {
+                    for value in &mut data[current_row_index] {
+                        *value = value.checked_neg()?;
+                    }
+                }
+                reduce_by_greatest_common_divisor(&mut data[current_row_index])?;
+
+                // Eliminate this column in all other rows:
+                let current_row = data[current_row_index].clone();
+                let pivot = current_row[current_column_index];
+                for (row_index, row) in data.iter_mut().enumerate() {
+                    let factor = row[current_column_index];
+                    if row_index == current_row_index || factor == 0 {
+                        continue;
+                    }
+                    for (row_value, current_row_value) in row.iter_mut().zip(&current_row) {
+                        *row_value = pivot
+                            .checked_mul(*row_value)?
+                            .checked_sub(factor.checked_mul(*current_row_value)?)?;
+                    }
+                    reduce_by_greatest_common_divisor(row)?;
+                }
+
+                current_row_index += 1;
+                current_column_index += 1;
+            }
+            independent_variables
+                .extend((current_column_index..variables_count).map(VariableIndex));
+
+            Some(SystemOfLinearEquations {
+                data,
+                state: Eliminated {
+                    dependent_variables,
+                    independent_variables,
+                },
+            })
+        }
+    }
+
+    /// Returns `None` if the divisor is `2^127`, which does not fit into an `i128`.
+    fn reduce_by_greatest_common_divisor(row: &mut [i128]) -> Option<()> {
+        let greatest_common_divisor = row.iter().fold(0, |divisor, value| {
+            let (mut a, mut b) = (divisor, value.unsigned_abs());
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        });
+        if greatest_common_divisor > 1 {
+            let greatest_common_divisor = i128::try_from(greatest_common_divisor).ok()?;
+            row.iter_mut()
+                .for_each(|value| *value /= greatest_common_divisor);
+        }
+        Some(())
+    }
+
+    impl<Value> SystemOfLinearEquations<Eliminated, Value> {
         pub fn dependent_variables(&self) -> &[VariableIndex] {
             &self.state.dependent_variables
         }
@@ -312,7 +420,49 @@ mod solver {
         pub fn independent_variables(&self) -> &[VariableIndex] {
             &self.state.independent_variables
         }
+    }
 
+    impl SystemOfLinearEquations<Eliminated, i128> {
+        /// Returns `None` if a dependent is not an integer for the given independents or its
+        /// calculation overflows.
+        pub fn calculate_dependents(
+            &self,
+            independent_variable_values: &[i128],
+        ) -> Option<Box<[i128]>> {
+            assert_eq!(
+                independent_variable_values.len(),
+                self.state.independent_variables.len()
+            );
+            self.state
+                .dependent_variables
+                .iter()
+                .enumerate()
+                .map(|(equation_index, dependent_variable_index)| {
+                    let equation = &self.data[equation_index];
+                    let numerator = self
+                        .state
+                        .independent_variables
+                        .iter()
+                        .zip(independent_variable_values)
+                        .try_fold(
+                            *equation.last().expect("Should get result value"),
+                            |result, (independent_variable_index, independent_variable_value)| {
+                                result.checked_sub(
+                                    equation[independent_variable_index.0]
+                                        .checked_mul(*independent_variable_value)?,
+                                )
+                            },
+                        )?;
+                    let pivot = equation[dependent_variable_index.0];
+                    (numerator.checked_rem(pivot)? == 0)
+                        .then(|| numerator.checked_div(pivot))
+                        .flatten()
+                })
+                .collect()
+        }
+    }
+
+    impl SystemOfLinearEquations<Eliminated, f64> {
         pub fn calculate_dependents(&self, independent_variable_values: &[f64]) -> Box<[f64]> {
             assert_eq!(
                 independent_variable_values.len(),
@@ -339,14 +489,17 @@ mod solver {
         }
     }
 
-    fn maximum_value_str_length(data: &Vec<Vec<f64>>) -> usize {
+    fn maximum_value_str_length<Value: Display>(data: &[Vec<Value>]) -> usize {
         data.iter()
             .flat_map(|row| row.iter().map(|value| value.to_string().len()))
             .max()
             .unwrap_or(0)
     }
 
-    fn display_initial(data: &Vec<Vec<f64>>, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn display_initial<Value: Display>(
+        data: &[Vec<Value>],
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
         let maximum_value_str_length = maximum_value_str_length(data);
         for row in data {
             for (column_index, cell) in row.iter().enumerate() {
@@ -361,13 +514,13 @@ mod solver {
         Ok(())
     }
 
-    impl Display for SystemOfLinearEquations<Initial> {
+    impl<Value: Display> Display for SystemOfLinearEquations<Initial, Value> {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             display_initial(&self.data, f)
         }
     }
 
-    impl Display for SystemOfLinearEquations<Eliminated> {
+    impl<Value: Display> Display for SystemOfLinearEquations<Eliminated, Value> {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             display_initial(&self.data, f)?;
             let maximum_value_str_length = maximum_value_str_length(&self.data);
@@ -499,7 +652,88 @@ mod count {
     }
 }
 
-fn determine_fewest_button_presses_to_configure_joltage_levels(manual: Manual) -> u64 {
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+enum Elimination {
+    /// Gaussian elimination on `f64`, rounding the dependents.
+    Float,
+    /// Fraction-free elimination on `i128`.
+    #[default]
+    Exact,
+}
+
+impl FromStr for Elimination {
+    type Err = ParseEliminationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "float" => Ok(Self::Float),
+            "exact" => Ok(Self::Exact),
+            _ => Err(ParseEliminationError::Unknown {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum ParseEliminationError {
+    #[error("Unknown elimination '{value}'")]
+    Unknown { value: String },
+}
+
+enum EliminatedSystem {
+    Float(SystemOfLinearEquations<Eliminated, f64>),
+    Exact(SystemOfLinearEquations<Eliminated, i128>),
+}
+
+impl EliminatedSystem {
+    fn dependent_variables(&self) -> &[VariableIndex] {
+        match self {
+            EliminatedSystem::Float(system) => system.dependent_variables(),
+            EliminatedSystem::Exact(system) => system.dependent_variables(),
+        }
+    }
+
+    fn independent_variables(&self) -> &[VariableIndex] {
+        match self {
+            EliminatedSystem::Float(system) => system.independent_variables(),
+            EliminatedSystem::Exact(system) => system.independent_variables(),
+        }
+    }
+
+    /// Returns `None` if a dependent is negative or not an integer for the given independents.
+    fn calculate_dependents(&self, independents: &[u64]) -> Option<Box<[u64]>> {
+        match self {
+            EliminatedSystem::Float(system) => system
+                .calculate_dependents(&independents.iter().map(|v| *v as f64).collect::<Box<[_]>>())
+                .iter()
+                .map(|dependent| {
+                    let rounded = dependent.round();
+                    if *dependent < -1e-9 || (dependent - rounded).abs() > 1e-9 {
+                        None
+                    } else {
+                        Some(rounded as u64)
+                    }
+                })
+                .collect(),
+            EliminatedSystem::Exact(system) => system
+                .calculate_dependents(
+                    &independents
+                        .iter()
+                        .map(|v| i128::from(*v))
+                        .collect::<Box<[_]>>(),
+                )?
+                .iter()
+                .map(|dependent| u64::try_from(*dependent).ok())
+                .collect(),
+        }
+    }
+}
+
+fn determine_fewest_button_presses_to_configure_joltage_levels(
+    manual: Manual,
+    elimination: Elimination,
+) -> u64 {
     let machine_count = manual.0.len();
     manual
         .0
@@ -509,26 +743,49 @@ fn determine_fewest_button_presses_to_configure_joltage_levels(manual: Manual) -
             // TODO println!();
             // TODO println!("MACHINE: #{index} / {machine_count}");
 
-            let system = SystemOfLinearEquations::new_with_fn(
-                EquationsCount::try_from(machine_description.joltage_requirements.0.len()).unwrap(),
+            let equations_count =
+                EquationsCount::try_from(machine_description.joltage_requirements.0.len()).unwrap();
+            let variables_count =
                 VariablesCount::try_from(machine_description.button_wiring_schematics.len())
-                    .unwrap(),
-                |joltage_index, button_index| {
-                    if machine_description.button_wiring_schematics[button_index.0]
-                        .0
-                        .iter()
-                        .any(|wiring_target| wiring_target.index == joltage_index.0)
-                    {
-                        1.
-                    } else {
-                        0.
-                    }
-                },
-                |joltage_index| {
-                    machine_description.joltage_requirements.0[joltage_index.0].0 as f64
-                },
-            )
-            .gaussian_elimination();
+                    .unwrap();
+            let is_wired = |joltage_index: EquationIndex, button_index: VariableIndex| {
+                machine_description.button_wiring_schematics[button_index.0]
+                    .0
+                    .iter()
+                    .any(|wiring_target| wiring_target.index == joltage_index.0)
+            };
+            let joltage = |joltage_index: EquationIndex| {
+                machine_description.joltage_requirements.0[joltage_index.0].0
+            };
+            let float_system = || {
+                EliminatedSystem::Float(
+                    SystemOfLinearEquations::new_with_fn(
+                        equations_count,
+                        variables_count,
+                        |joltage_index, button_index| {
+                            if is_wired(joltage_index, button_index) {
+                                1.
+                            } else {
+                                0.
+                            }
+                        },
+                        |joltage_index| joltage(joltage_index) as f64,
+                    )
+                    .gaussian_elimination(),
+                )
+            };
+            let system = match elimination {
+                Elimination::Float => float_system(),
+                // Falls back to floats for the rare systems overflowing `i128` while eliminating.
+                Elimination::Exact => SystemOfLinearEquations::new_with_fn(
+                    equations_count,
+                    variables_count,
+                    |joltage_index, button_index| i128::from(is_wired(joltage_index, button_index)),
+                    |joltage_index| i128::from(joltage(joltage_index)),
+                )
+                .fraction_free_elimination()
+                .map_or_else(float_system, EliminatedSystem::Exact),
+            };
 
             // TODO println!("{:?}", system.independent_variables());
             let maximum_presses_per_button = system
@@ -549,21 +806,7 @@ fn determine_fewest_button_presses_to_configure_joltage_levels(manual: Manual) -
             let solution = bounded_inclusive(maximum_presses_per_button)
                 .filter_map(|independents| {
                     // TODO println!("{independents:?}");
-                    let dependants = system.calculate_dependents(
-                        &independents.iter().map(|v| *v as f64).collect::<Box<[_]>>(),
-                    );
-                    let dependants = {
-                        let mut new_dependants = Vec::with_capacity(dependants.len());
-                        for dependent in dependants {
-                            let rounded = dependent.round();
-                            if dependent < -1e-9 || (dependent - rounded).abs() > 1e-9 {
-                                return None;
-                            } else {
-                                new_dependants.push(rounded as u64);
-                            }
-                        }
-                        new_dependants.into_boxed_slice()
-                    };
+                    let dependants = system.calculate_dependents(&independents)?;
 
                     let all = system
                         .independent_variables()
@@ -1107,9 +1350,80 @@ mod tests {
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
 
         // Act
-        let part2 = part2(input);
+        let part2 = part2(input, Elimination::default());
 
         // Assert
         assert_eq!(part2, 33);
     }
+
+    #[test]
+    fn test_fraction_free_elimination() {
+        // Arrange
+        // 2a + 3b +  c = 7
+        // 4a + 3b + 3c = 9
+        let coefficients = [[2, 3, 1, 7], [4, 3, 3, 9]];
+        let system = SystemOfLinearEquations::new_with_fn(
+            EquationsCount::try_from(2).unwrap(),
+            VariablesCount::try_from(3).unwrap(),
+            |equation_index, variable_index| coefficients[equation_index.0][variable_index.0],
+            |equation_index| coefficients[equation_index.0][3],
+        );
+
+        // Act
+        let system = system
+            .fraction_free_elimination()
+            .expect("Should not overflow");
+
+        // Assert
+        assert_eq!(
+            system.dependent_variables(),
+            [VariableIndex(0), VariableIndex(1)]
+        );
+        assert_eq!(system.independent_variables(), [VariableIndex(2)]);
+        // a = 1 - c and b = (5 + c) / 3
+        assert_eq!(system.calculate_dependents(&[1]), Some([0, 2].into()));
+        assert_eq!(system.calculate_dependents(&[4]), Some([-3, 3].into()));
+        assert_eq!(system.calculate_dependents(&[0]), None);
+        assert_eq!(system.calculate_dependents(&[i128::MAX]), None);
+    }
+
+    #[test]
+    fn test_fraction_free_elimination_overflow() {
+        // Arrange
+        let coefficients = [[(1 << 100) + 1, 3, 1], [(1 << 100) + 3, 5, 1]];
+        let system = SystemOfLinearEquations::new_with_fn(
+            EquationsCount::try_from(2).unwrap(),
+            VariablesCount::try_from(2).unwrap(),
+            |equation_index, variable_index| coefficients[equation_index.0][variable_index.0],
+            |equation_index| coefficients[equation_index.0][2],
+        );
+
+        // Act
+        let system = system.fraction_free_elimination();
+
+        // Assert
+        assert!(system.is_none());
+    }
+
+    #[test]
+    fn test_part2_eliminations() {
+        // Arrange
+        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+        // Act
+        let float = determine_fewest_button_presses_to_configure_joltage_levels(
+            input.parse().expect("Should parse"),
+            Elimination::Float,
+        );
+        let exact = determine_fewest_button_presses_to_configure_joltage_levels(
+            input.parse().expect("Should parse"),
+            Elimination::Exact,
+        );
+
+        // Assert
+        assert_eq!(float, 33);
+        assert_eq!(exact, 33);
+    }
 }
@@ -0,0 +1,116 @@
+/// Disjoint sets of the elements `0..len` with union by rank and path compression, so finding
+/// and merging sets take amortised nearly constant time.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl DisjointSet {
+    /// Creates `len` sets containing one element each.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            set_count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the count of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Returns the representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while self.parents[current] != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `first` and `second` and returns whether they were disjoint.
+    pub fn union(&mut self, first: usize, second: usize) -> bool {
+        let (first_root, second_root) = (self.find(first), self.find(second));
+        if first_root == second_root {
+            return false;
+        }
+        let (root, child) = if self.ranks[first_root] < self.ranks[second_root] {
+            (second_root, first_root)
+        } else {
+            (first_root, second_root)
+        };
+        self.parents[child] = root;
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.sizes[root] += self.sizes[child];
+        self.set_count -= 1;
+        true
+    }
+
+    /// Returns the size of the set containing `element`.
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Returns the elements of every set, ordered by their smallest element.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut set_indices = vec![None; self.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.set_count);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            let set_index = *set_indices[root].get_or_insert_with(|| {
+                sets.push(Vec::with_capacity(self.sizes[root]));
+                sets.len() - 1
+            });
+            sets[set_index].push(element);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_find() {
+        // Arrange
+        let mut set = DisjointSet::new(6);
+
+        // Act
+        let merged = [
+            set.union(0, 1),
+            set.union(2, 3),
+            set.union(1, 3),
+            set.union(0, 2),
+        ];
+
+        // Assert
+        assert_eq!(merged, [true, true, true, false]);
+        assert_eq!(set.set_count(), 3);
+        assert_eq!(set.find(3), set.find(0));
+        assert_ne!(set.find(4), set.find(0));
+        assert_eq!(set.size_of(2), 4);
+        assert_eq!(set.size_of(5), 1);
+        assert_eq!(set.sets(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+}
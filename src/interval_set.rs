@@ -0,0 +1,212 @@
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+use num_traits::PrimInt;
+
+/// A set of integers stored as sorted, disjoint and non-adjacent inclusive ranges.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Returns whether `value` is inside one of the ranges in `O(log n)`.
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the count of integers inside the set.
+    pub fn count(&self) -> u128
+    where
+        T: Into<u128>,
+    {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| end.into() - start.into() + 1)
+            .sum()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Returns the ranges between the ranges of this set, excluding everything before the first
+    /// and after the last range.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges
+            .iter()
+            .tuple_windows()
+            .map(|(&(_, previous_end), &(next_start, _))| {
+                previous_end + T::one()..=next_start - T::one()
+            })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_sorted(
+            self.ranges
+                .iter()
+                .merge_by(&other.ranges, |left, right| left.0 <= right.0)
+                .copied(),
+        )
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut left_index, mut right_index) = (0, 0);
+        while let (Some(&(left_start, left_end)), Some(&(right_start, right_end))) =
+            (self.ranges.get(left_index), other.ranges.get(right_index))
+        {
+            let start = left_start.max(right_start);
+            let end = left_end.min(right_end);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if left_end < right_end {
+                left_index += 1;
+            } else {
+                right_index += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut other_index = 0;
+        for &(start, end) in &self.ranges {
+            while other
+                .ranges
+                .get(other_index)
+                .is_some_and(|&(_, other_end)| other_end < start)
+            {
+                other_index += 1;
+            }
+            let mut remaining_start = Some(start);
+            for &(other_start, other_end) in other.ranges[other_index..]
+                .iter()
+                .take_while(|&&(other_start, _)| other_start <= end)
+            {
+                let Some(current_start) = remaining_start else {
+                    break;
+                };
+                if other_start > current_start {
+                    ranges.push((current_start, other_start - T::one()));
+                }
+                remaining_start = other_end.checked_add(&T::one());
+            }
+            if let Some(current_start) = remaining_start
+                && current_start <= end
+            {
+                ranges.push((current_start, end));
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Coalesces overlapping and adjacent ranges, which have to be sorted by their start.
+    fn from_sorted(sorted: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut ranges: Vec<(T, T)> = Vec::new();
+        for (start, end) in sorted {
+            if start > end {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some((_, last_end))
+                    if last_end
+                        .checked_add(&T::one())
+                        .is_none_or(|after_last_end| start <= after_last_end) =>
+                {
+                    *last_end = (*last_end).max(end);
+                }
+                _ => ranges.push((start, end)),
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    /// Builds the set in `O(n log n)` by sorting the ranges and coalescing them in one pass.
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut ranges = iter
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+        Self::from_sorted(ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_iter_coalesces() {
+        // Arrange
+        let ranges = [
+            10..=14,
+            3..=5,
+            16..=20,
+            12..=18,
+            6..=6,
+            RangeInclusive::new(30, 29),
+        ];
+
+        // Act
+        let set = ranges.into_iter().collect::<IntervalSet<u64>>();
+
+        // Assert
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![3..=6, 10..=20]);
+        assert_eq!(set.count(), 15);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![7..=9]);
+        assert!(set.contains(3) && set.contains(6) && set.contains(15));
+        assert!(!set.contains(2) && !set.contains(8) && !set.contains(21));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        // Arrange
+        let left = [0..=10, 20..=30, 40..=u8::MAX]
+            .into_iter()
+            .collect::<IntervalSet<u8>>();
+        let right = [5..=25, 35..=45, 50..=50]
+            .into_iter()
+            .collect::<IntervalSet<u8>>();
+
+        // Act
+        let union = left.union(&right);
+        let intersection = left.intersection(&right);
+        let difference = left.difference(&right);
+
+        // Assert
+        assert_eq!(
+            union.ranges().collect::<Vec<_>>(),
+            vec![0..=30, 35..=u8::MAX]
+        );
+        assert_eq!(
+            intersection.ranges().collect::<Vec<_>>(),
+            vec![5..=10, 20..=25, 40..=45, 50..=50]
+        );
+        assert_eq!(
+            difference.ranges().collect::<Vec<_>>(),
+            vec![0..=4, 26..=30, 46..=49, 51..=u8::MAX]
+        );
+        assert_eq!(
+            right.difference(&left).ranges().collect::<Vec<_>>(),
+            vec![11..=19, 35..=39]
+        );
+        assert_eq!(union.count(), 31 + 221);
+    }
+}
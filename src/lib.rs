@@ -1,3 +1,7 @@
+pub mod disjoint_set;
+pub mod interval_set;
+pub mod number_type;
+
 #[macro_export]
 macro_rules! aoc {
     ($input_str:ident, $func_ident:ident) => {
@@ -40,6 +44,21 @@ macro_rules! aoc {
         }
     };
     ($input_str:ident, $func_1_ident:ident, $func_2_ident: ident) => {
+        $crate::aoc!(@parts $func_1_ident($input_str), $func_2_ident($input_str))
+    };
+    // Passes the options read once in `main` after the input, like `part1(rule), part2(rule)`.
+    (
+        $input_str:ident,
+        $func_1_ident:ident($($argument_1:expr),* $(,)?),
+        $func_2_ident:ident($($argument_2:expr),* $(,)?)
+    ) => {
+        $crate::aoc!(
+            @parts
+            $func_1_ident($input_str $(, $argument_1)*),
+            $func_2_ident($input_str $(, $argument_2)*)
+        )
+    };
+    (@parts $part_1:expr, $part_2:expr) => {
         #[cfg(feature = "benchmark")]
         {
             const MINIMUM_DURATION: f64 = 3.0;
@@ -50,11 +69,11 @@ macro_rules! aoc {
                 let start = std::time::Instant::now();
                 #[cfg(feature = "part1")]
                 {
-                    let _answer = $func_1_ident($input_str);
+                    let _answer = $part_1;
                 }
                 #[cfg(feature = "part2")]
                 {
-                    let _answer = $func_2_ident($input_str);
+                    let _answer = $part_2;
                 }
                 let end = std::time::Instant::now();
                 let duration = end - start;
@@ -75,12 +94,12 @@ macro_rules! aoc {
             let start = std::time::Instant::now();
             #[cfg(feature = "part1")]
             {
-                let answer = $func_1_ident($input_str);
+                let answer = $part_1;
                 println!("The answer to part 1 is: {answer}");
             }
             #[cfg(feature = "part2")]
             {
-                let answer = $func_2_ident($input_str);
+                let answer = $part_2;
                 println!("The answer to part 2 is: {answer}");
             }
             #[cfg(feature = "internal_timings")]
@@ -94,3 +113,9 @@ macro_rules! aoc {
         }
     };
 }
+
+/// Returns the value of the command line option `--<name>=<value>`, if given.
+pub fn option(name: &str) -> Option<String> {
+    let prefix = format!("--{name}=");
+    std::env::args().find_map(|argument| argument.strip_prefix(&prefix).map(str::to_string))
+}
@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+
+/// The number type to calculate in, selectable as `u64`, `u128` or `big` for overflowing inputs.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum NumberType {
+    #[default]
+    U64,
+    U128,
+    Big,
+}
+
+impl FromStr for NumberType {
+    type Err = ParseNumberTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u64" => Ok(Self::U64),
+            "u128" => Ok(Self::U128),
+            "big" => Ok(Self::Big),
+            _ => Err(ParseNumberTypeError::Unknown {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParseNumberTypeError {
+    #[error("Unknown number type '{value}'")]
+    Unknown { value: String },
+}
+
+/// A number calculated in one of the [`NumberType`]s.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum AnyNumber {
+    U64(u64),
+    U128(u128),
+    Big(BigUint),
+}
+
+impl Display for AnyNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnyNumber::U64(number) => write!(f, "{number}"),
+            AnyNumber::U128(number) => write!(f, "{number}"),
+            AnyNumber::Big(number) => write!(f, "{number}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number_type() {
+        // Arrange
+        let values = ["u64", "u128", "big", "i32"];
+
+        // Act
+        let [u64, u128, big, unknown] = values.map(NumberType::from_str);
+
+        // Assert
+        assert_eq!(u64.expect("Should parse"), NumberType::U64);
+        assert_eq!(u128.expect("Should parse"), NumberType::U128);
+        assert_eq!(big.expect("Should parse"), NumberType::Big);
+        assert!(matches!(
+            unknown,
+            Err(ParseNumberTypeError::Unknown { value }) if value == "i32"
+        ));
+    }
+}